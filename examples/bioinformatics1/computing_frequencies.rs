@@ -1,5 +1,6 @@
 extern crate bio;
 
+use bio::alphabet::DnaAlphabet;
 use bio::strings::frequency_array;
 
 /// Code Challenge: Implement ComputingFrequencies to generate a frequency array.
@@ -13,7 +14,7 @@ fn main() {
     bio::io::read_line(&mut kstr);
     let k = kstr.parse::<usize>().unwrap();
 
-    let res = frequency_array(&text, k);
+    let res = frequency_array::<DnaAlphabet>(&text, k);
 
     bio::io::print_vec(&res);
 }
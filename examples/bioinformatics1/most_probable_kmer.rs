@@ -25,7 +25,7 @@ fn main() {
     let pt = bio::io::parse_vec::<f64>(&lines[5]).unwrap();
     let profile = Profile::new(pa, pc, pg, pt);
 
-    let (_, kmer) = most_probable_kmer(&dna, k, &profile);
+    let (_, kmer) = most_probable_kmer(&dna, k, &profile).expect("no unmasked k-mer");
 
     println!("{}", kmer);
 }
@@ -1,5 +1,6 @@
 extern crate bio;
 
+use bio::alphabet::DnaAlphabet;
 use bio::strings::neighbors;
 
 /// Code Challenge: Implement Neighbors to find the d-neighborhood of a string.
@@ -14,7 +15,7 @@ fn main() {
     bio::io::read_line(&mut d_string);
     let d = d_string.parse::<usize>().unwrap();
 
-    let mut res = neighbors(&pattern, d);
+    let mut res = neighbors::<DnaAlphabet>(&pattern, d);
     // sort to be able to compare with saved result
     res.sort();
 
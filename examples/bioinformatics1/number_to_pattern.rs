@@ -1,5 +1,6 @@
 extern crate bio;
 
+use bio::alphabet::DnaAlphabet;
 use bio::strings::number_to_pattern;
 
 fn main() {
@@ -12,7 +13,7 @@ fn main() {
     let index = index_string.parse::<usize>().unwrap();
     let k = k_string.parse::<usize>().unwrap();
 
-    let res = number_to_pattern(index, k);
+    let res = number_to_pattern::<DnaAlphabet>(index, k);
 
     println!("{}", res);
 }
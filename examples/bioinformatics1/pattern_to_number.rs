@@ -1,5 +1,6 @@
 extern crate bio;
 
+use bio::alphabet::DnaAlphabet;
 use bio::strings::pattern_to_number;
 
 fn main() {
@@ -7,7 +8,7 @@ fn main() {
     let mut pattern = String::new();
     bio::io::read_line(&mut pattern);
 
-    let res = pattern_to_number(pattern.as_bytes());
+    let res = pattern_to_number::<DnaAlphabet>(pattern.as_bytes());
 
     println!("{}", res);
 }
@@ -1,14 +1,36 @@
-use std::mem;
+//! DNA over the `Nuc` enum, so that a sequence cannot hold anything other
+//! than a nucleotide code.
+
+use std::convert::TryFrom;
+use std::fmt;
+use std::iter::FromIterator;
+use std::ops;
+use std::slice;
 use std::str;
+use std::vec;
 
-/// Nucleic Acid Code
+use ::alphabet::{Alphabet, DnaAlphabet};
+use ::dna::{self, iupac, AsciiError};
+
+/// Nucleic Acid Code, including IUPAC ambiguity codes
 #[repr(u8)]
-#[derive(Copy, Clone, PartialOrd, PartialEq, Eq, Ord)]
+#[derive(Debug, Copy, Clone, PartialOrd, PartialEq, Eq, Ord, Hash)]
 pub enum Nuc {
-    A = 'A' as u8,
-    C = 'C' as u8,
-    G = 'G' as u8,
-    T = 'T' as u8,
+    A = b'A',
+    B = b'B',
+    C = b'C',
+    D = b'D',
+    G = b'G',
+    H = b'H',
+    K = b'K',
+    M = b'M',
+    N = b'N',
+    R = b'R',
+    S = b'S',
+    T = b'T',
+    V = b'V',
+    W = b'W',
+    Y = b'Y',
 }
 
 
@@ -18,35 +40,101 @@ impl Nuc {
         nuc as u8
     }
 
-    pub fn from_utf8(x: u8) -> Nuc {
+    /// Nucleotide for the uppercase code `x`, `None` if `x` is not one of
+    /// `ACGT` or the IUPAC ambiguity codes.
+    pub fn from_utf8(x: u8) -> Option<Nuc> {
         use self::Nuc::*;
-        match x as char {
-            'A' => A,
-            'C' => C,
-            'G' => G,
-            'T' => T,
-            c => panic!("Unsupported Nuc: '{}'", c),
-        }
+        let nuc = match x {
+            b'A' => A,
+            b'B' => B,
+            b'C' => C,
+            b'D' => D,
+            b'G' => G,
+            b'H' => H,
+            b'K' => K,
+            b'M' => M,
+            b'N' => N,
+            b'R' => R,
+            b'S' => S,
+            b'T' => T,
+            b'V' => V,
+            b'W' => W,
+            b'Y' => Y,
+            _ => return None,
+        };
+        Some(nuc)
+    }
+
+    /// Nucleotide `A`, `C`, `G` or `T` with the given `DnaAlphabet` rank.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rank` is not less than 4.
+    pub fn from_rank(rank: usize) -> Nuc {
+        Nuc::from_utf8(DnaAlphabet::symbol(rank)).expect("DNA symbol is a nucleotide code")
+    }
+
+    /// `DnaAlphabet` rank of `nuc`, `None` for the ambiguity codes.
+    pub fn rank(nuc: Nuc) -> Option<usize> {
+        DnaAlphabet::rank(nuc as u8)
     }
 
-    pub unsafe fn from_utf8_unchecked(x: u8) -> Nuc {
-        mem::transmute(x)
+    pub fn complement(nuc: Nuc) -> Nuc {
+        Nuc::from_utf8(iupac::complement(nuc as u8)).expect("IUPAC complement of a nucleotide code")
     }
 
-    pub fn complement(nuc: Nuc) -> Nuc {
-        use self::Nuc::*;
-        match nuc {
-            A => T,
-            T => A,
-            G => C,
-            C => G,
-        }
+    /// Returns `true` if `a` and `b` may denote the same nucleotide.
+    pub fn matches(a: Nuc, b: Nuc) -> bool {
+        iupac::matches(a as u8, b as u8)
+    }
+
+    /// Returns `true` for the ambiguity codes, `false` for `A`, `C`, `G`
+    /// and `T`.
+    pub fn is_ambiguous(nuc: Nuc) -> bool {
+        Nuc::rank(nuc).is_none()
     }
 
 }
 
+impl From<Nuc> for u8 {
+    fn from(nuc: Nuc) -> u8 {
+        nuc as u8
+    }
+}
 
-#[derive(PartialOrd, PartialEq, Eq, Ord)]
+impl TryFrom<u8> for Nuc {
+    type Error = AsciiError;
+
+    fn try_from(x: u8) -> Result<Nuc, AsciiError> {
+        Nuc::from_utf8(x).ok_or_else(|| AsciiError::new(0, x))
+    }
+}
+
+impl fmt::Display for Nuc {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", *self as u8 as char)
+    }
+}
+
+
+/// DNA abstraction over a vector of `Nuc`.
+///
+/// Unlike `dna::Dna`, which is a byte vector, every element is a valid
+/// nucleotide code, so bytes are checked once on construction.
+///
+/// # Examples
+///
+/// ```
+/// use bio::adt::dna::{Dna, Nuc};
+///
+/// let dna = "ACGTN".parse::<Dna>().unwrap();
+/// assert_eq!(dna[1], Nuc::C);
+/// assert_eq!(dna.reverse_complement().to_string(), "NACGT");
+///
+/// let err = "ACGU".parse::<Dna>().unwrap_err();
+/// assert_eq!((err.valid_up_to(), err.byte()), (3, b'U'));
+/// ```
+#[derive(Debug, Clone, Default, PartialOrd, PartialEq, Eq, Ord, Hash)]
 pub struct Dna {
     vec: Vec<Nuc>,
 }
@@ -57,35 +145,36 @@ impl Dna {
     // Dna construction
     ////////////////////////////////////////////////////////////////////////////
 
-    pub fn from_slice(s: &[u8]) -> Dna {
-        let vec = s.iter().cloned()
-            .map(Nuc::from_utf8)
-            .collect();
-        Dna { vec: vec }
+    pub fn new(vec: Vec<Nuc>) -> Dna {
+        Dna { vec }
     }
 
-    pub unsafe fn from_slice_unchecked(s: &[u8]) -> Dna {
-        let vec: &[Nuc] = mem::transmute(s);
-        Dna { vec: vec.to_vec() }
-    }
-
-    pub fn from_str(s: &str) -> Dna {
-        Dna::from_slice(s.as_bytes())
+    /// Creates `Dna` from bytes, checking that every byte is an uppercase
+    /// nucleotide or ambiguity code.
+    pub fn parse_from_slice(s: &[u8]) -> Result<Dna, AsciiError> {
+        let mut vec = Vec::with_capacity(s.len());
+        for (i, &x) in s.iter().enumerate() {
+            match Nuc::from_utf8(x) {
+                Some(nuc) => vec.push(nuc),
+                None => return Err(AsciiError::new(i, x)),
+            }
+        }
+        Ok(Dna { vec })
     }
 
-    pub unsafe fn from_str_unchecked(s: &str) -> Dna {
-        Dna::from_slice_unchecked(s.as_bytes())
+    pub fn parse_from_str(s: &str) -> Result<Dna, AsciiError> {
+        Dna::parse_from_slice(s.as_bytes())
     }
 
     ////////////////////////////////////////////////////////////////////////////
     // Dna conversion
     ////////////////////////////////////////////////////////////////////////////
 
-    // fn into_vec(self) -> Vec<Nuc> {
-    //     self.vec
-    // }
+    pub fn into_vec(self) -> Vec<Nuc> {
+        self.vec
+    }
 
-    fn as_slice(&self) -> &[Nuc] {
+    pub fn as_slice(&self) -> &[Nuc] {
         self.vec.as_slice()
     }
 
@@ -95,27 +184,15 @@ impl Dna {
             .collect()
     }
 
-    pub unsafe fn to_utf8_unchecked(&self) -> &[u8] {
-        mem::transmute(self.vec.as_slice())
-    }
-
-    pub fn to_string(&self) -> String {
-        unsafe { String::from_utf8_unchecked(self.to_utf8()) }
-    }
-
-    pub unsafe fn to_string_unchecked(&self) -> String {
-        let vec: Vec<u8> = mem::transmute(self.vec.clone());
-        String::from_utf8_unchecked(vec)
+    /// Converts to the byte vector `dna::Dna`.
+    pub fn to_dna(&self) -> dna::Dna {
+        dna::Dna::new(self.to_utf8())
     }
 
     ////////////////////////////////////////////////////////////////////////////
     // Public functions
     ////////////////////////////////////////////////////////////////////////////
 
-    pub fn len(&self) -> usize {
-        self.vec.len()
-    }
-
     pub fn complement(&self) -> Dna {
         let cmp = self.vec.iter().cloned()
             .map(Nuc::complement)
@@ -144,110 +221,177 @@ impl Dna {
     }
 }
 
-#[cfg(ignore)]
+impl AsRef<[Nuc]> for Dna {
+    fn as_ref(&self) -> &[Nuc] {
+        self
+    }
+}
+
+impl ops::Deref for Dna {
+    type Target = [Nuc];
+
+    fn deref(&self) -> &[Nuc] {
+        &self.vec
+    }
+}
+
+impl fmt::Display for Dna {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let bytes = self.to_utf8();
+        fmt::Display::fmt(str::from_utf8(&bytes).expect("nucleotides should be ASCII"), f)
+    }
+}
+
+impl str::FromStr for Dna {
+    type Err = AsciiError;
+
+    fn from_str(s: &str) -> Result<Dna, AsciiError> {
+        Dna::parse_from_slice(s.as_bytes())
+    }
+}
+
+impl TryFrom<&dna::Dna> for Dna {
+    type Error = AsciiError;
+
+    fn try_from(dna: &dna::Dna) -> Result<Dna, AsciiError> {
+        Dna::parse_from_slice(dna)
+    }
+}
+
+impl From<&Dna> for dna::Dna {
+    fn from(dna: &Dna) -> dna::Dna {
+        dna.to_dna()
+    }
+}
+
+impl FromIterator<Nuc> for Dna {
+    fn from_iter<I: IntoIterator<Item = Nuc>>(iter: I) -> Dna {
+        Dna { vec: iter.into_iter().collect() }
+    }
+}
+
+impl IntoIterator for Dna {
+    type Item = Nuc;
+    type IntoIter = vec::IntoIter<Nuc>;
+
+    fn into_iter(self) -> vec::IntoIter<Nuc> {
+        self.vec.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Dna {
+    type Item = &'a Nuc;
+    type IntoIter = slice::Iter<'a, Nuc>;
+
+    fn into_iter(self) -> slice::Iter<'a, Nuc> {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
 mod tests {
 
+    use std::collections::HashSet;
+    use std::convert::TryFrom;
+
     use test::Bencher;
 
-    use super::Dna;
+    use super::{Dna, Nuc};
     use data::Dataset;
-    use hamming_distance;
+    use dna;
+    use seq::hamming_distance;
 
     static SAMPLE: &'static str = "ACTATGCGACT";
     static COMPLEMENT: &'static str = "TGATACGCTGA";
 
     #[test]
-    fn from_str() {
-        let dna = Dna::from_str(SAMPLE);
-        assert_eq!(SAMPLE.to_string(), dna.to_string());
-    }
-
-    #[test]
-    fn from_str_unchecked() {
-        let dna = unsafe { Dna::from_str_unchecked(SAMPLE) };
+    fn test_from_str() {
+        let dna = SAMPLE.parse::<Dna>().unwrap();
         assert_eq!(SAMPLE.to_string(), dna.to_string());
+        assert_eq!(dna.len(), SAMPLE.len());
     }
 
     #[test]
-    fn from_slice() {
-        let bytes = SAMPLE.as_bytes();
-        let dna = Dna::from_slice(bytes);
-        assert_eq!(SAMPLE.to_string(), dna.to_string());
+    fn test_parse() {
+        let err = Dna::parse_from_slice(b"ACgt").unwrap_err();
+        assert_eq!((err.valid_up_to(), err.byte()), (2, b'g'));
+        let err = Nuc::try_from(b'U').unwrap_err();
+        assert_eq!((err.valid_up_to(), err.byte()), (0, b'U'));
+        assert_eq!(Nuc::try_from(b'R').unwrap(), Nuc::R);
     }
 
     #[test]
-    fn from_slice_unchecked() {
-        let bytes = SAMPLE.as_bytes();
-        let dna = unsafe { Dna::from_slice_unchecked(bytes) };
-        assert_eq!(SAMPLE.to_string(), dna.to_string())
+    fn test_rank() {
+        for r in 0..4 {
+            assert_eq!(Nuc::rank(Nuc::from_rank(r)), Some(r));
+        }
+        assert_eq!(Nuc::rank(Nuc::N), None);
+        assert_eq!(Nuc::complement(Nuc::B), Nuc::V);
+        assert!(Nuc::is_ambiguous(Nuc::W));
     }
 
     #[test]
-    fn to_utf8() {
-        let dna = Dna::from_str(SAMPLE);
+    fn test_to_utf8() {
+        let dna = Dna::parse_from_str(SAMPLE).unwrap();
         assert_eq!(SAMPLE.as_bytes().to_owned(), dna.to_utf8());
     }
 
     #[test]
-    fn to_utf8_unchecked() {
-        let dna = Dna::from_str(SAMPLE);
-        let dna_bytes = unsafe { dna.to_utf8_unchecked() };
-        assert_eq!(SAMPLE.as_bytes(), dna_bytes);
+    fn test_reverse_complement() {
+        let dna = Dna::parse_from_str(SAMPLE).unwrap();
+        let reverse_complement: String = COMPLEMENT.chars().rev().collect();
+        assert_eq!(reverse_complement, dna.reverse_complement().to_string());
     }
 
     #[test]
-    fn to_string_unchecked() {
-        let dna = Dna::from_str(SAMPLE);
-        assert_eq!(SAMPLE.to_owned(), unsafe { dna.to_string_unchecked() })
+    fn test_interop() {
+        let bytes = dna::Dna::from_str("ACGTRYN");
+        let nucs = Dna::try_from(&bytes).unwrap();
+        assert_eq!(dna::Dna::from(&nucs), bytes);
+        assert!(Dna::try_from(&dna::Dna::from_str("ACGU")).is_err());
     }
 
     #[test]
-    fn reverse_complement() {
-        let dna = Dna::from_str(SAMPLE);
-        let reverse_complement: String = COMPLEMENT.chars().rev().collect();
-        assert_eq!(reverse_complement, dna.reverse_complement().to_string());
-    }
+    fn test_iter() {
+        let dna = Dna::parse_from_str("ACGTN").unwrap();
+        let ambiguous: Vec<_> = dna.iter().cloned().filter(|&n| Nuc::is_ambiguous(n)).collect();
+        assert_eq!(ambiguous, [Nuc::N]);
+        let collected: Dna = dna.clone().into_iter().map(Nuc::complement).collect();
+        assert_eq!(collected, dna.complement());
 
-    #[bench]
-    fn bench_from_str(b: &mut Bencher) {
-        let dataset = Dataset::open_fasta("data/Salmonella_enterica.txt");
-        b.iter(|| Dna::from_str(dataset.contents()));
+        let set: HashSet<Dna> = vec![dna.clone(), dna.complement(), dna].into_iter().collect();
+        assert_eq!(set.len(), 2);
     }
 
     #[bench]
-    fn bench_from_str_unchecked(b: &mut Bencher) {
-        let dataset = Dataset::open_fasta("data/Salmonella_enterica.txt");
-        b.iter(|| unsafe { Dna::from_str_unchecked(dataset.contents()) });
+    fn bench_parse_from_str(b: &mut Bencher) {
+        let dataset = Dataset::open_text("data/bioinformatics1/reverse_complement/dataset_3_2.txt");
+        let lines = dataset.lines();
+        b.iter(|| Dna::parse_from_str(lines[0]).unwrap());
     }
 
     #[bench]
     fn bench_to_string(b: &mut Bencher) {
-        let dataset = Dataset::open_fasta("data/Salmonella_enterica.txt");
-        let dna = unsafe { Dna::from_str_unchecked(dataset.contents()) };
-        b.iter(move || dna.to_string());
-    }
-
-    #[bench]
-    fn bench_to_string_unchecked(b: &mut Bencher) {
-        let dataset = Dataset::open_fasta("data/Salmonella_enterica.txt");
-        let dna = unsafe { Dna::from_str_unchecked(dataset.contents()) };
-        b.iter(|| unsafe { dna.to_string_unchecked() });
+        let dataset = Dataset::open_text("data/bioinformatics1/reverse_complement/dataset_3_2.txt");
+        let lines = dataset.lines();
+        let dna = Dna::parse_from_str(lines[0]).unwrap();
+        b.iter(|| dna.to_string());
     }
 
     #[bench]
     fn bench_reverse_complement(b: &mut Bencher) {
-        let dataset = Dataset::open_text("data/reverse_complement/dataset_3_2.txt");
+        let dataset = Dataset::open_text("data/bioinformatics1/reverse_complement/dataset_3_2.txt");
         let lines = dataset.lines();
-        let dna = Dna::from_str(lines[0]);
+        let dna = Dna::parse_from_str(lines[0]).unwrap();
         b.iter(|| dna.reverse_complement())
     }
 
     #[bench]
     fn bench_find(b: &mut Bencher) {
-        let dataset = Dataset::open_text("data/approximate_pattern_count/dataset_9_6.txt");
+        let dataset = Dataset::open_text("data/bioinformatics1/approximate_pattern_count/dataset_9_6.txt");
         let lines = dataset.lines();
-        let pat = unsafe { Dna::from_str_unchecked(lines[0]) };
-        let dna = unsafe { Dna::from_str_unchecked(lines[1]) };
+        let pat = Dna::parse_from_str(lines[0]).unwrap();
+        let dna = Dna::parse_from_str(lines[1]).unwrap();
         let d = lines[2].parse::<usize>().unwrap();
         b.iter(|| dna.find(&pat, |chunk, pat| hamming_distance(chunk, pat) <= d))
     }
@@ -1,3 +1,5 @@
+//! Algebraic data types for sequences.
+
 pub mod dna;
 
-pub use self::dna::Dna;
+pub use self::dna::{Dna, Nuc};
@@ -0,0 +1,117 @@
+//! Alphabets of biological sequences.
+//!
+//! An alphabet ranks its symbols, so k-mers can be numbered and counted
+//! in arrays, see `strings::pattern_to_number` and `strings::frequency_array`,
+//! and motif profiles have a row per symbol, see `dna::Profile`.
+
+/// Alphabet of sequence symbols, used as a type parameter of generic
+/// algorithms.
+///
+/// # Examples
+///
+/// ```
+/// use bio::alphabet::{Alphabet, DnaAlphabet, ProteinAlphabet};
+///
+/// assert_eq!(DnaAlphabet::size(), 4);
+/// assert_eq!(DnaAlphabet::rank(b'G'), Some(2));
+/// assert_eq!(DnaAlphabet::complement(b'G'), Some(b'C'));
+/// assert_eq!(ProteinAlphabet::symbol(0), b'A');
+/// assert_eq!(ProteinAlphabet::complement(b'A'), None);
+/// ```
+pub trait Alphabet {
+    /// Symbols in the order of their ranks.
+    const SYMBOLS: &'static [u8];
+
+    /// Number of symbols.
+    fn size() -> usize {
+        Self::SYMBOLS.len()
+    }
+
+    /// Rank of `symbol`, `None` if it does not belong to the alphabet.
+    fn rank(symbol: u8) -> Option<usize> {
+        Self::SYMBOLS.iter().position(|&s| s == symbol)
+    }
+
+    /// Symbol with the given `rank`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rank` is not less than `size()`.
+    fn symbol(rank: usize) -> u8 {
+        Self::SYMBOLS[rank]
+    }
+
+    /// Complementary symbol, for alphabets with complementary pairs.
+    fn complement(_symbol: u8) -> Option<u8> {
+        None
+    }
+}
+
+/// DNA nucleotides `A`, `C`, `G`, `T`, ranked in lexicographic order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DnaAlphabet;
+
+impl Alphabet for DnaAlphabet {
+    const SYMBOLS: &'static [u8] = b"ACGT";
+
+    fn rank(symbol: u8) -> Option<usize> {
+        match symbol {
+            b'A' => Some(0),
+            b'C' => Some(1),
+            b'G' => Some(2),
+            b'T' => Some(3),
+            _ => None,
+        }
+    }
+
+    fn complement(symbol: u8) -> Option<u8> {
+        DnaAlphabet::rank(symbol).map(|r| Self::SYMBOLS[3 - r])
+    }
+}
+
+/// RNA nucleotides `A`, `C`, `G`, `U`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RnaAlphabet;
+
+impl Alphabet for RnaAlphabet {
+    const SYMBOLS: &'static [u8] = b"ACGU";
+
+    fn complement(symbol: u8) -> Option<u8> {
+        RnaAlphabet::rank(symbol).map(|r| Self::SYMBOLS[3 - r])
+    }
+}
+
+/// The 20 standard amino acids in one-letter codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ProteinAlphabet;
+
+impl Alphabet for ProteinAlphabet {
+    const SYMBOLS: &'static [u8] = b"ACDEFGHIKLMNPQRSTVWY";
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{Alphabet, DnaAlphabet, ProteinAlphabet, RnaAlphabet};
+
+    fn check_ranks<A: Alphabet>() {
+        for (i, &s) in A::SYMBOLS.iter().enumerate() {
+            assert_eq!(A::rank(s), Some(i));
+            assert_eq!(A::symbol(i), s);
+            if let Some(c) = A::complement(s) {
+                assert_eq!(A::complement(c), Some(s));
+            }
+        }
+        assert_eq!(A::rank(b'*'), None);
+    }
+
+    #[test]
+    fn test_ranks() {
+        check_ranks::<DnaAlphabet>();
+        check_ranks::<RnaAlphabet>();
+        check_ranks::<ProteinAlphabet>();
+        assert_eq!(RnaAlphabet::complement(b'A'), Some(b'U'));
+        assert_eq!(ProteinAlphabet::size(), 20);
+    }
+
+}
@@ -0,0 +1,213 @@
+//!
+//! BED format reader and writer
+//!
+//! Reads and writes the first six BED columns: chrom, start, end, name,
+//! score and strand. BED positions are 0-based half-open, the same as
+//! `Interval`.
+//!
+
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+use super::{Lines, Result};
+use super::interval::{Interval, Strand};
+
+/// Iterator over intervals of a BED file. `track`, `browser` and comment
+/// lines are skipped.
+///
+/// # Examples
+///
+/// ```
+/// use bio::data::bed::Reader;
+/// use bio::data::interval::Strand;
+///
+/// let input = b"track name=hits\nchr1\t10\t19\tDnaA_box\t0\t-\nchr2\t0\t5\n";
+/// let intervals: Vec<_> = Reader::new(&input[..]).map(|i| i.unwrap()).collect();
+///
+/// assert_eq!(intervals[0].start, 10);
+/// assert_eq!(intervals[0].name, Some("DnaA_box".to_owned()));
+/// assert_eq!(intervals[0].strand, Strand::Reverse);
+/// assert_eq!(intervals[1].chrom, "chr2");
+/// assert_eq!(intervals[1].name, None);
+/// ```
+pub struct Reader<R> {
+    lines: Lines<R>,
+}
+
+impl<R: BufRead> Reader<R> {
+
+    pub fn new(reader: R) -> Reader<R> {
+        Reader { lines: Lines::new(reader) }
+    }
+
+    fn read_interval(&mut self) -> Result<Option<Interval>> {
+        loop {
+            if !self.lines.read_line()? {
+                return Ok(None);
+            }
+            let line = self.lines.line.trim();
+            if !(line.is_empty() || line.starts_with('#') ||
+                 line.starts_with("track") || line.starts_with("browser")) {
+                break;
+            }
+        }
+
+        let line = self.lines.line.trim();
+        let fields: Vec<_> = if line.contains('\t') {
+            line.split('\t').map(|f| f.trim()).collect()
+        } else {
+            line.split_whitespace().collect()
+        };
+        if fields.len() < 3 {
+            return Err(self.lines.format_error("expected at least 3 columns"));
+        }
+        let start = fields[1].parse::<usize>()
+            .map_err(|_| self.lines.format_error("invalid start position"))?;
+        let end = fields[2].parse::<usize>()
+            .map_err(|_| self.lines.format_error("invalid end position"))?;
+        if start > end {
+            return Err(self.lines.format_error("start position is past the end"));
+        }
+
+        let mut interval = Interval::new(fields[0], start, end);
+        if let Some(&name) = fields.get(3) {
+            if name != "." {
+                interval.name = Some(name.to_owned());
+            }
+        }
+        if let Some(&score) = fields.get(4) {
+            if score != "." {
+                let score = score.parse::<f64>()
+                    .map_err(|_| self.lines.format_error("invalid score"))?;
+                interval.score = Some(score);
+            }
+        }
+        if let Some(&strand) = fields.get(5) {
+            interval.strand = Strand::from_symbol(strand)
+                .ok_or_else(|| self.lines.format_error("invalid strand"))?;
+        }
+        Ok(Some(interval))
+    }
+
+}
+
+impl Reader<Box<dyn BufRead>> {
+
+    /// Opens BED file at `path`, possibly gzip compressed.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Reader<Box<dyn BufRead>>> {
+        let path = path.as_ref();
+        let mut reader = Reader::new(super::open(path)?);
+        reader.lines.path = Some(path.to_path_buf());
+        Ok(reader)
+    }
+
+}
+
+impl<R: BufRead> Iterator for Reader<R> {
+    type Item = Result<Interval>;
+
+    fn next(&mut self) -> Option<Result<Interval>> {
+        match self.read_interval() {
+            Ok(Some(interval)) => Some(Ok(interval)),
+            Ok(None) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+/// Writes intervals as six column BED lines. BED scores are integers from
+/// 0 to 1000, so scores are rounded to the nearest integer and clamped to
+/// this range, missing scores are written as 0.
+///
+/// # Examples
+///
+/// ```
+/// use bio::data::bed::Writer;
+/// use bio::data::interval::{Interval, Strand};
+///
+/// let hits = Interval::from_hits("NC_003198", &[12, 40], 9, "DnaA_box", Strand::Forward);
+/// let mut out = Vec::new();
+/// Writer::new(&mut out).write_all(&hits).unwrap();
+///
+/// assert_eq!(String::from_utf8(out).unwrap(),
+///            "NC_003198\t12\t21\tDnaA_box\t0\t+\nNC_003198\t40\t49\tDnaA_box\t0\t+\n");
+/// ```
+pub struct Writer<W> {
+    writer: W,
+}
+
+impl<W: Write> Writer<W> {
+
+    pub fn new(writer: W) -> Writer<W> {
+        Writer { writer }
+    }
+
+    /// Writes `track` line, used by genome browsers to name the track.
+    pub fn write_track(&mut self, name: &str) -> io::Result<()> {
+        writeln!(self.writer, "track name=\"{}\"", name)
+    }
+
+    pub fn write(&mut self, interval: &Interval) -> io::Result<()> {
+        let name = interval.name.as_ref().map_or(".", |n| n.as_str());
+        let score = interval.score.map_or(0., |s| s.round().clamp(0., 1000.)) as u32;
+        writeln!(self.writer, "{}\t{}\t{}\t{}\t{}\t{}",
+                 interval.chrom, interval.start, interval.end, name, score, interval.strand)
+    }
+
+    pub fn write_all(&mut self, intervals: &[Interval]) -> io::Result<()> {
+        for interval in intervals.iter() {
+            self.write(interval)?;
+        }
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{Reader, Writer};
+    use data::interval::{Interval, Strand};
+
+    #[test]
+    fn test_roundtrip() {
+        let mut intervals = Interval::from_hits("chr1", &[3, 17], 4, "hit", Strand::Reverse);
+        intervals[0].score = Some(12.);
+        intervals.push(Interval::new("chr2", 0, 10));
+        let mut out = Vec::new();
+        {
+            let mut writer = Writer::new(&mut out);
+            writer.write_track("hits").unwrap();
+            writer.write_all(&intervals).unwrap();
+        }
+        let mut read: Vec<_> = Reader::new(&out[..]).map(|i| i.unwrap()).collect();
+        // BED always has a score column
+        read[1].score = None;
+        read[2].score = None;
+        assert_eq!(read, intervals);
+    }
+
+    #[test]
+    fn test_write_score() {
+        let mut intervals = Interval::from_hits("chr1", &[0, 5, 9], 2, "hit", Strand::Forward);
+        intervals[0].score = Some(12.5);
+        intervals[1].score = Some(-3.);
+        intervals[2].score = Some(1e6);
+        let mut out = Vec::new();
+        Writer::new(&mut out).write_all(&intervals).unwrap();
+        let scores: Vec<_> = Reader::new(&out[..]).map(|i| i.unwrap().score).collect();
+        assert_eq!(scores, [Some(13.), Some(0.), Some(1000.)]);
+    }
+
+    #[test]
+    fn test_invalid_line() {
+        let input = b"chr1\t1\t5\nchr1\t9\t3\n";
+        let err = Reader::new(&input[..]).find(|r| r.is_err()).unwrap().unwrap_err();
+        assert_eq!(err.line(), Some(2));
+    }
+
+}
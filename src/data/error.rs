@@ -0,0 +1,141 @@
+//!
+//! Errors of dataset and sequence file readers
+//!
+
+use std::error;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::result;
+use std::str::Utf8Error;
+
+pub type Result<T> = result::Result<T, Error>;
+
+/// Errors which can occur when reading a dataset or a sequence file.
+///
+/// Every error carries the path of the file, when it is known, and the
+/// 1-based line number for errors found while parsing.
+#[derive(Debug)]
+pub enum Error {
+    /// Failure of the underlying reader.
+    Io {
+        path: Option<PathBuf>,
+        err: io::Error,
+    },
+    /// Input is not valid UTF-8. `valid_up_to` is the byte index within
+    /// the line, or within the file when the line is unknown.
+    Encoding {
+        path: Option<PathBuf>,
+        line: Option<usize>,
+        valid_up_to: usize,
+    },
+    /// Input does not follow the expected file format.
+    Format {
+        path: Option<PathBuf>,
+        line: usize,
+        message: String,
+    },
+    /// Binary input does not follow the expected file format, `offset`
+    /// is the position of the invalid data in bytes.
+    Binary {
+        path: Option<PathBuf>,
+        offset: u64,
+        message: String,
+    },
+    /// Record requested by name, such as a sequence of a 2bit file, is
+    /// not in the file.
+    NotFound {
+        path: Option<PathBuf>,
+        name: String,
+    },
+}
+
+impl Error {
+
+    pub fn format(line: usize, message: &str) -> Error {
+        Error::Format { path: None, line, message: message.to_owned() }
+    }
+
+    pub fn binary(offset: u64, message: &str) -> Error {
+        Error::Binary { path: None, offset, message: message.to_owned() }
+    }
+
+    pub fn not_found(name: &str) -> Error {
+        Error::NotFound { path: None, name: name.to_owned() }
+    }
+
+    pub fn encoding(line: Option<usize>, err: &Utf8Error) -> Error {
+        Error::Encoding { path: None, line, valid_up_to: err.valid_up_to() }
+    }
+
+    /// Path of the file which caused the error.
+    pub fn path(&self) -> Option<&Path> {
+        match *self {
+            Error::Io { ref path, .. } |
+            Error::Encoding { ref path, .. } |
+            Error::Format { ref path, .. } |
+            Error::Binary { ref path, .. } |
+            Error::NotFound { ref path, .. } => path.as_ref().map(|p| p.as_path()),
+        }
+    }
+
+    /// Line number where the error was found.
+    pub fn line(&self) -> Option<usize> {
+        match *self {
+            Error::Io { .. } | Error::Binary { .. } | Error::NotFound { .. } => None,
+            Error::Encoding { line, .. } => line,
+            Error::Format { line, .. } => Some(line),
+        }
+    }
+
+    /// Attaches file path to the error, unless it is already known.
+    pub fn with_path<P: AsRef<Path>>(mut self, file: P) -> Error {
+        match self {
+            Error::Io { ref mut path, .. } |
+            Error::Encoding { ref mut path, .. } |
+            Error::Format { ref mut path, .. } |
+            Error::Binary { ref mut path, .. } |
+            Error::NotFound { ref mut path, .. } => {
+                if path.is_none() {
+                    *path = Some(file.as_ref().to_path_buf());
+                }
+            },
+        }
+        self
+    }
+
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(path) = self.path() {
+            write!(f, "{}: ", path.display())?;
+        }
+        if let Some(line) = self.line() {
+            write!(f, "line {}: ", line)?;
+        }
+        match *self {
+            Error::Io { ref err, .. } => write!(f, "{}", err),
+            Error::Encoding { valid_up_to, .. } =>
+                write!(f, "invalid utf-8: invalid byte near index {}", valid_up_to),
+            Error::Format { ref message, .. } => write!(f, "{}", message),
+            Error::Binary { offset, ref message, .. } => write!(f, "offset {}: {}", offset, message),
+            Error::NotFound { ref name, .. } => write!(f, "no record '{}'", name),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            Error::Io { ref err, .. } => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Error {
+        Error::Io { path: None, err }
+    }
+}
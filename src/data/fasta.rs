@@ -0,0 +1,291 @@
+//!
+//! FASTA format reader and writer
+//!
+//! A FASTA file is a sequence of records, each one starts with a header
+//! line `>id description` followed by any number of sequence lines.
+//!
+
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+use ::dna::Dna;
+use super::{Lines, Result};
+
+/// Single FASTA record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    /// Identifier, the first word of the header line.
+    pub id: String,
+    /// Rest of the header line after identifier, if any.
+    pub desc: Option<String>,
+    /// Sequence lines of the record joined together.
+    pub seq: Dna,
+}
+
+impl Record {
+
+    pub fn new(id: &str, desc: Option<&str>, seq: Dna) -> Record {
+        Record {
+            id: id.to_owned(),
+            desc: desc.map(|d| d.to_owned()),
+            seq,
+        }
+    }
+
+    /// Parse header line without the leading `>`.
+    fn from_header(header: &str) -> Record {
+        let (id, desc) = super::split_header(header);
+        Record::new(id, desc, Dna::new(Vec::new()))
+    }
+
+}
+
+/// Iterator over records of a FASTA file.
+///
+/// Blank lines and Windows line endings are skipped. Lowercase
+/// (soft-masked) bases are converted to upper case unless the reader
+/// was switched to `preserve_case`.
+///
+/// # Examples
+///
+/// ```
+/// use bio::data::fasta::Reader;
+///
+/// let input = b">seq1 first\nACGT\nacgt\n\n>seq2\r\nTTGA\r\n";
+/// let records: Vec<_> = Reader::new(&input[..])
+///     .map(|r| r.unwrap())
+///     .collect();
+///
+/// assert_eq!(records.len(), 2);
+/// assert_eq!(records[0].id, "seq1");
+/// assert_eq!(records[0].desc, Some("first".to_owned()));
+/// assert_eq!(records[0].seq.as_str(), "ACGTACGT");
+/// assert_eq!(records[1].id, "seq2");
+/// assert_eq!(records[1].seq.as_str(), "TTGA");
+/// ```
+pub struct Reader<R> {
+    lines: Lines<R>,
+    header: Option<String>,
+    uppercase: bool,
+}
+
+impl<R: BufRead> Reader<R> {
+
+    pub fn new(reader: R) -> Reader<R> {
+        Reader {
+            lines: Lines::new(reader),
+            header: None,
+            uppercase: true,
+        }
+    }
+
+    /// Keep lowercase (soft-masked) bases as is.
+    pub fn preserve_case(mut self) -> Reader<R> {
+        self.uppercase = false;
+        self
+    }
+
+    /// Skips blank lines until the first header.
+    fn read_header(&mut self) -> Result<Option<String>> {
+        while self.lines.read_line()? {
+            let line = self.lines.line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            return match line.strip_prefix('>') {
+                Some(header) => Ok(Some(header.to_owned())),
+                None => Err(self.lines.format_error("expected FASTA header starting with '>'")),
+            };
+        }
+        Ok(None)
+    }
+
+    fn read_record(&mut self) -> Result<Option<Record>> {
+        let header = match self.header.take() {
+            Some(header) => header,
+            None => match self.read_header()? {
+                Some(header) => header,
+                None => return Ok(None),
+            },
+        };
+
+        let mut record = Record::from_header(&header);
+        let mut seq = Vec::new();
+        while self.lines.read_line()? {
+            let line = self.lines.line.trim();
+            if let Some(header) = line.strip_prefix('>') {
+                self.header = Some(header.to_owned());
+                break;
+            }
+            seq.extend(line.bytes().filter(|b| !b.is_ascii_whitespace()));
+        }
+        if self.uppercase {
+            seq.make_ascii_uppercase();
+        }
+        record.seq = Dna::new(seq);
+        Ok(Some(record))
+    }
+
+}
+
+impl Reader<Box<dyn BufRead>> {
+
+    /// Opens FASTA file at `path`, possibly gzip compressed.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Reader<Box<dyn BufRead>>> {
+        let path = path.as_ref();
+        let mut reader = Reader::new(super::open(path)?);
+        reader.lines.path = Some(path.to_path_buf());
+        Ok(reader)
+    }
+
+}
+
+impl<R: BufRead> Iterator for Reader<R> {
+    type Item = Result<Record>;
+
+    fn next(&mut self) -> Option<Result<Record>> {
+        match self.read_record() {
+            Ok(Some(record)) => Some(Ok(record)),
+            Ok(None) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+/// Default number of bases per line of `Writer`.
+pub const LINE_WIDTH: usize = 60;
+
+/// Writes sequences in FASTA format.
+///
+/// # Examples
+///
+/// ```
+/// use bio::data::fasta::{Record, Writer};
+/// use bio::dna::Dna;
+///
+/// let mut out = Vec::new();
+/// {
+///     let mut writer = Writer::with_width(&mut out, 4);
+///     let record = Record::new("contig_1", Some("length=10"), Dna::from_str("ACGTACGTAC"));
+///     writer.write(&record).unwrap();
+///     writer.write_seq("ori", b"TTATCCACA").unwrap();
+/// }
+/// assert_eq!(out, &b">contig_1 length=10\nACGT\nACGT\nAC\n>ori\nTTAT\nCCAC\nA\n"[..]);
+/// ```
+pub struct Writer<W> {
+    writer: W,
+    width: usize,
+}
+
+impl<W: Write> Writer<W> {
+
+    /// Creates writer wrapping sequence lines at `LINE_WIDTH` bases.
+    pub fn new(writer: W) -> Writer<W> {
+        Writer::with_width(writer, LINE_WIDTH)
+    }
+
+    /// Creates writer wrapping sequence lines at `width` bases, `0`
+    /// writes every sequence on a single line.
+    pub fn with_width(writer: W, width: usize) -> Writer<W> {
+        Writer { writer, width }
+    }
+
+    pub fn write(&mut self, record: &Record) -> io::Result<()> {
+        match record.desc {
+            Some(ref desc) => self.write_seq(&format!("{} {}", record.id, desc), &record.seq),
+            None => self.write_seq(&record.id, &record.seq),
+        }
+    }
+
+    /// Writes sequence with the given header text (without `>`).
+    pub fn write_seq(&mut self, header: &str, seq: &[u8]) -> io::Result<()> {
+        writeln!(self.writer, ">{}", header)?;
+        if self.width == 0 {
+            self.writer.write_all(seq)?;
+            return self.writer.write_all(b"\n");
+        }
+        for line in seq.chunks(self.width) {
+            self.writer.write_all(line)?;
+            self.writer.write_all(b"\n")?;
+        }
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{Reader, Record, Writer};
+    use dna::Dna;
+    use data::Error;
+
+    #[test]
+    fn test_multiple_records() {
+        let input = b"\n>chr1 contig one\nACGT\nAC\n>chr2\n\nGG\nTT\n>empty\n";
+        let records: Vec<_> = Reader::new(&input[..])
+            .collect::<Result<_, _>>()
+            .unwrap();
+        let ids: Vec<_> = records.iter().map(|r| r.id.as_str()).collect();
+        let seqs: Vec<_> = records.iter().map(|r| r.seq.as_str()).collect();
+        assert_eq!(ids, ["chr1", "chr2", "empty"]);
+        assert_eq!(seqs, ["ACGTAC", "GGTT", ""]);
+        assert_eq!(records[0].desc, Some("contig one".to_owned()));
+        assert_eq!(records[1].desc, None);
+    }
+
+    #[test]
+    fn test_crlf_and_soft_masked() {
+        let input = b">r1\r\nacGT\r\nNnaa\r\n";
+        let record = Reader::new(&input[..]).next().unwrap().unwrap();
+        assert_eq!(record.seq.as_str(), "ACGTNNAA");
+
+        let record = Reader::new(&input[..]).preserve_case().next().unwrap().unwrap();
+        assert_eq!(record.seq.as_str(), "acGTNnaa");
+    }
+
+    #[test]
+    fn test_write_read() {
+        let records = vec![
+            Record::new("r1", Some("first record"), Dna::from_str("ACGTTGCAACGT")),
+            Record::new("r2", None, Dna::from_str("GATTACA")),
+        ];
+        for &width in [0, 1, 5, 60].iter() {
+            let mut out = Vec::new();
+            {
+                let mut writer = Writer::with_width(&mut out, width);
+                for record in records.iter() {
+                    writer.write(record).unwrap();
+                }
+            }
+            let read: Vec<_> = Reader::new(&out[..]).map(|r| r.unwrap()).collect();
+            assert_eq!(read, records);
+        }
+    }
+
+    #[test]
+    fn test_missing_header() {
+        let input = b"\nACGT\n>r1\nACGT\n";
+        let mut reader = Reader::new(&input[..]);
+        let err = reader.next().unwrap().unwrap_err();
+        assert_eq!(err.line(), Some(2));
+    }
+
+    #[test]
+    fn test_invalid_utf8() {
+        let input = b">r1\nAC\xffGT\n";
+        let mut reader = Reader::new(&input[..]);
+        match reader.next() {
+            Some(Err(Error::Encoding { line, valid_up_to, .. })) => {
+                assert_eq!(line, Some(2));
+                assert_eq!(valid_up_to, 2);
+            },
+            _ => panic!("expected encoding error"),
+        }
+    }
+
+}
@@ -0,0 +1,375 @@
+//!
+//! FASTQ format reader and writer
+//!
+//! Every FASTQ record takes four lines: `@id description`, sequence,
+//! `+` separator and the base qualities encoded as ASCII characters.
+//!
+
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+use ::dna::Dna;
+use super::{Lines, Result};
+
+/// Offset of ASCII encoded Phred quality scores.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phred {
+    /// Sanger and Illumina 1.8+, scores start at `!`.
+    Offset33,
+    /// Illumina 1.3 to 1.7, scores start at `@`.
+    Offset64,
+}
+
+impl Phred {
+
+    pub fn offset(self) -> u8 {
+        match self {
+            Phred::Offset33 => 33,
+            Phred::Offset64 => 64,
+        }
+    }
+
+    /// Guess encoding from the quality characters. Characters below `;`
+    /// never appear in Phred+64 data, and characters above `J` are
+    /// unusual for Phred+33. Returns `None` when `qual` fits both.
+    pub fn detect(qual: &[u8]) -> Option<Phred> {
+        if qual.iter().any(|&q| q < b';') {
+            Some(Phred::Offset33)
+        } else if qual.iter().any(|&q| q > b'J') {
+            Some(Phred::Offset64)
+        } else {
+            None
+        }
+    }
+
+    /// Decode ASCII quality character to the Phred score.
+    pub fn decode(self, q: u8) -> Option<u8> {
+        if q >= self.offset() && q <= b'~' {
+            Some(q - self.offset())
+        } else {
+            None
+        }
+    }
+
+    /// Encode Phred score as ASCII quality character.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the score does not fit into printable ASCII. See
+    /// `checked_encode` for the fallible version.
+    pub fn encode(self, score: u8) -> u8 {
+        self.checked_encode(score)
+            .unwrap_or_else(|| panic!("Phred score {} is out of range", score))
+    }
+
+    /// Encode Phred score as ASCII quality character, `None` when the
+    /// score does not fit into printable ASCII.
+    pub fn checked_encode(self, score: u8) -> Option<u8> {
+        score.checked_add(self.offset()).filter(|&q| q <= b'~')
+    }
+
+}
+
+/// Single FASTQ record, qualities are stored as decoded Phred scores.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub id: String,
+    pub desc: Option<String>,
+    pub seq: Dna,
+    pub qual: Vec<u8>,
+}
+
+impl Record {
+
+    /// Creates new record.
+    ///
+    /// # Panics
+    ///
+    /// Panics when sequence and quality lengths differ.
+    pub fn new(id: &str, desc: Option<&str>, seq: Dna, qual: Vec<u8>) -> Record {
+        assert_eq!(seq.len(), qual.len());
+        Record {
+            id: id.to_owned(),
+            desc: desc.map(|d| d.to_owned()),
+            seq,
+            qual,
+        }
+    }
+
+}
+
+/// Iterator over records of a FASTQ file.
+///
+/// Unless the encoding is given explicitly, it is detected once from the
+/// quality characters of the first record, falling back to Phred+33 when
+/// they fit both encodings, and then used for the whole file. Use
+/// `with_phred` for Phred+64 files whose first record is ambiguous.
+///
+/// # Examples
+///
+/// ```
+/// use bio::data::fastq::{Phred, Reader};
+///
+/// let input = b"@r1 sample\nACGT\n+\nII#!\n";
+/// let mut reader = Reader::new(&input[..]);
+/// let record = reader.next().unwrap().unwrap();
+///
+/// assert_eq!(record.id, "r1");
+/// assert_eq!(record.seq.as_str(), "ACGT");
+/// assert_eq!(record.qual, [40, 40, 2, 0]);
+/// assert_eq!(reader.phred(), Some(Phred::Offset33));
+/// ```
+pub struct Reader<R> {
+    lines: Lines<R>,
+    phred: Option<Phred>,
+}
+
+impl<R: BufRead> Reader<R> {
+
+    pub fn new(reader: R) -> Reader<R> {
+        Reader {
+            lines: Lines::new(reader),
+            phred: None,
+        }
+    }
+
+    /// Creates reader with the known quality encoding.
+    pub fn with_phred(reader: R, phred: Phred) -> Reader<R> {
+        Reader { phred: Some(phred), ..Reader::new(reader) }
+    }
+
+    /// Quality encoding, if it is already known.
+    pub fn phred(&self) -> Option<Phred> {
+        self.phred
+    }
+
+    fn read_record(&mut self) -> Result<Option<Record>> {
+        loop {
+            if !self.lines.read_line()? {
+                return Ok(None);
+            }
+            if !self.lines.line.trim().is_empty() {
+                break;
+            }
+        }
+
+        let header = match self.lines.line.strip_prefix('@') {
+            Some(header) => header.to_owned(),
+            None => return Err(self.lines.format_error("expected header starting with '@'")),
+        };
+        let (id, desc) = super::split_header(&header);
+
+        self.lines.expect_line("sequence")?;
+        let mut seq = self.lines.line.trim().as_bytes().to_vec();
+        seq.make_ascii_uppercase();
+
+        self.lines.expect_line("'+' separator")?;
+        match self.lines.line.strip_prefix('+') {
+            Some(repeat) => {
+                let (repeat_id, _) = super::split_header(repeat);
+                if !repeat_id.is_empty() && repeat_id != id {
+                    return Err(self.lines.format_error("separator does not match record id"));
+                }
+            },
+            None => return Err(self.lines.format_error("expected '+' separator")),
+        }
+
+        self.lines.expect_line("quality")?;
+        let qual = self.lines.line.trim().as_bytes();
+        if qual.len() != seq.len() {
+            return Err(self.lines.format_error("quality and sequence lengths differ"));
+        }
+        let phred = *self.phred.get_or_insert(Phred::detect(qual).unwrap_or(Phred::Offset33));
+        let mut scores = Vec::with_capacity(qual.len());
+        for &q in qual {
+            match phred.decode(q) {
+                Some(score) => scores.push(score),
+                None => {
+                    let message = format!("invalid quality character '{}'", q as char);
+                    return Err(self.lines.format_error(&message));
+                },
+            }
+        }
+
+        Ok(Some(Record::new(id, desc, Dna::new(seq), scores)))
+    }
+
+}
+
+impl Reader<Box<dyn BufRead>> {
+
+    /// Opens FASTQ file at `path`, possibly gzip compressed.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Reader<Box<dyn BufRead>>> {
+        let path = path.as_ref();
+        let mut reader = Reader::new(super::open(path)?);
+        reader.lines.path = Some(path.to_path_buf());
+        Ok(reader)
+    }
+
+}
+
+impl<R: BufRead> Iterator for Reader<R> {
+    type Item = Result<Record>;
+
+    fn next(&mut self) -> Option<Result<Record>> {
+        match self.read_record() {
+            Ok(Some(record)) => Some(Ok(record)),
+            Ok(None) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+/// Writes records in FASTQ format.
+///
+/// # Examples
+///
+/// ```
+/// use bio::data::fastq::{Record, Writer};
+/// use bio::dna::Dna;
+///
+/// let mut out = Vec::new();
+/// {
+///     let mut writer = Writer::new(&mut out);
+///     let record = Record::new("r1", None, Dna::from_str("ACG"), vec![40, 30, 0]);
+///     writer.write(&record).unwrap();
+/// }
+/// assert_eq!(out, b"@r1\nACG\n+\nI?!\n");
+/// ```
+pub struct Writer<W> {
+    writer: W,
+    phred: Phred,
+}
+
+impl<W: Write> Writer<W> {
+
+    /// Creates writer with Phred+33 encoding.
+    pub fn new(writer: W) -> Writer<W> {
+        Writer::with_phred(writer, Phred::Offset33)
+    }
+
+    pub fn with_phred(writer: W, phred: Phred) -> Writer<W> {
+        Writer { writer, phred }
+    }
+
+    pub fn write(&mut self, record: &Record) -> io::Result<()> {
+        match record.desc {
+            Some(ref desc) => self.write_seq(&format!("{} {}", record.id, desc), &record.seq, &record.qual),
+            None => self.write_seq(&record.id, &record.seq, &record.qual),
+        }
+    }
+
+    /// Writes sequence with the given header text (without `@`) and
+    /// Phred scores. Fails with `InvalidInput` when sequence and quality
+    /// lengths differ or a score can not be encoded, before anything is
+    /// written.
+    pub fn write_seq(&mut self, header: &str, seq: &[u8], qual: &[u8]) -> io::Result<()> {
+        let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidInput, message);
+        if seq.len() != qual.len() {
+            return Err(invalid(format!("{} bases but {} quality scores", seq.len(), qual.len())));
+        }
+        let mut encoded = Vec::with_capacity(qual.len());
+        for &score in qual {
+            match self.phred.checked_encode(score) {
+                Some(q) => encoded.push(q),
+                None => return Err(invalid(format!("Phred score {} is out of range", score))),
+            }
+        }
+        writeln!(self.writer, "@{}", header)?;
+        self.writer.write_all(seq)?;
+        self.writer.write_all(b"\n+\n")?;
+        self.writer.write_all(&encoded)?;
+        self.writer.write_all(b"\n")
+    }
+
+    /// Writes sequence without known qualities, every base gets the same
+    /// Phred `score`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bio::data::fastq::Writer;
+    /// use bio::dna::Dna;
+    ///
+    /// let mut out = Vec::new();
+    /// Writer::new(&mut out).write_dna("motif_1", &Dna::from_str("TTATCC"), 40).unwrap();
+    /// assert_eq!(out, b"@motif_1\nTTATCC\n+\nIIIIII\n");
+    /// ```
+    pub fn write_dna(&mut self, header: &str, dna: &[u8], score: u8) -> io::Result<()> {
+        self.write_seq(header, dna, &vec![score; dna.len()])
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::io::ErrorKind;
+
+    use super::{Phred, Reader, Writer};
+
+    #[test]
+    fn test_detect_phred64() {
+        let input = b"@r1\nACGT\n+r1\nhhB@\n@r2\nAC\n+\n@@\n";
+        let mut reader = Reader::new(&input[..]);
+        let r1 = reader.next().unwrap().unwrap();
+        assert_eq!(reader.phred(), Some(Phred::Offset64));
+        assert_eq!(r1.qual, [40, 40, 2, 0]);
+        let r2 = reader.next().unwrap().unwrap();
+        assert_eq!(r2.qual, [0, 0]);
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn test_detect_once() {
+        // the first record fits both encodings, so Phred+33 is kept even
+        // though the second one looks like Phred+64
+        let input = b"@r1\nAC\n+\nII\n@r2\nAC\n+\nhh\n";
+        let mut reader = Reader::new(&input[..]);
+        assert_eq!(reader.next().unwrap().unwrap().qual, [40, 40]);
+        assert_eq!(reader.phred(), Some(Phred::Offset33));
+        assert_eq!(reader.next().unwrap().unwrap().qual, [71, 71]);
+    }
+
+    #[test]
+    fn test_write_invalid() {
+        let mut out = Vec::new();
+        {
+            let mut writer = Writer::new(&mut out);
+            let err = writer.write_seq("r1", b"ACG", &[40, 40]).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidInput);
+            let err = writer.write_dna("r2", b"ACG", 94).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidInput);
+            writer.write_dna("r3", b"A", 93).unwrap();
+        }
+        assert_eq!(out, b"@r3\nA\n+\n~\n");
+    }
+
+    #[test]
+    fn test_malformed_records() {
+        let cases: [(&[u8], usize); 4] = [
+            (b"@r1\nACGT\n+\nIII\n", 4),
+            (b"@r1\nACGT\n+\nIIII\nr2\nAC\n+\nII\n", 5),
+            (b"@r1\nACGT\n-\nIIII\n", 3),
+            (b"@r1\nACGT\n", 3),
+        ];
+        for &(input, expected) in cases.iter() {
+            let err = Reader::new(input).find(|r| r.is_err()).unwrap().unwrap_err();
+            assert_eq!(err.line(), Some(expected));
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_phred64() {
+        let input = b"@r1 some read\nACGT\n+\nhhB@\n";
+        let record = Reader::with_phred(&input[..], Phred::Offset64).next().unwrap().unwrap();
+        let mut out = Vec::new();
+        Writer::with_phred(&mut out, Phred::Offset64).write(&record).unwrap();
+        assert_eq!(&out[..], &b"@r1 some read\nACGT\n+\nhhB@\n"[..]);
+    }
+
+}
@@ -0,0 +1,610 @@
+//!
+//! GenBank flat file reader
+//!
+//! Reads the header, the feature table and the sequence of GenBank
+//! records. Feature locations are converted to 0-based half-open
+//! intervals, the same way positions are reported by `seq::find`.
+//!
+
+use std::io::BufRead;
+use std::path::Path;
+use std::str;
+
+use ::dna::Dna;
+use super::{Lines, Result};
+
+/// Column where feature locations and qualifiers start.
+const QUALIFIER_COLUMN: usize = 21;
+
+/// Key of a feature table entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FeatureKind {
+    Source,
+    Gene,
+    Cds,
+    RRna,
+    TRna,
+    Other(String),
+}
+
+impl FeatureKind {
+
+    fn from_key(key: &str) -> FeatureKind {
+        match key {
+            "source" => FeatureKind::Source,
+            "gene" => FeatureKind::Gene,
+            "CDS" => FeatureKind::Cds,
+            "rRNA" => FeatureKind::RRna,
+            "tRNA" => FeatureKind::TRna,
+            other => FeatureKind::Other(other.to_owned()),
+        }
+    }
+
+}
+
+/// Feature location. Positions are 0-based, `end` is exclusive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Location {
+    /// Continuous range, partial flags mark `<` and `>` boundaries.
+    Range {
+        start: usize,
+        end: usize,
+        partial_start: bool,
+        partial_end: bool,
+    },
+    /// Zero-length site between two adjacent bases, at the 0-based
+    /// position of the second base, 123 for `123^124`.
+    Between(usize),
+    /// Location on the reverse strand.
+    Complement(Box<Location>),
+    /// Locations joined into one contiguous sequence, `join(...)`.
+    Join(Vec<Location>),
+    /// Locations in the given order, `order(...)`, without implying that
+    /// they are joined.
+    Order(Vec<Location>),
+}
+
+impl Location {
+
+    /// Leftmost position covered by the location.
+    pub fn start(&self) -> usize {
+        match *self {
+            Location::Range { start, .. } => start,
+            Location::Between(pos) => pos,
+            Location::Complement(ref loc) => loc.start(),
+            Location::Join(ref locs) |
+            Location::Order(ref locs) => locs.iter().map(|l| l.start()).min().unwrap_or(0),
+        }
+    }
+
+    /// Position past the rightmost base covered by the location.
+    pub fn end(&self) -> usize {
+        match *self {
+            Location::Range { end, .. } => end,
+            Location::Between(pos) => pos,
+            Location::Complement(ref loc) => loc.end(),
+            Location::Join(ref locs) |
+            Location::Order(ref locs) => locs.iter().map(|l| l.end()).max().unwrap_or(0),
+        }
+    }
+
+    /// Returns `true` if the location is on the reverse strand.
+    pub fn is_complement(&self) -> bool {
+        match *self {
+            Location::Range { .. } | Location::Between(_) => false,
+            Location::Complement(ref loc) => !loc.is_complement(),
+            Location::Join(ref locs) |
+            Location::Order(ref locs) => locs.iter().all(|l| l.is_complement()) && !locs.is_empty(),
+        }
+    }
+
+    /// Extracts the located sequence from `seq`, reverse complemented for
+    /// the reverse strand locations. Sites between bases are empty, parts
+    /// of `order(...)` are concatenated in order like those of a join, see
+    /// `Location::Order` for the parts themselves.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the location is out of `seq` bounds.
+    pub fn extract(&self, seq: &[u8]) -> Dna {
+        match *self {
+            Location::Range { start, end, .. } => Dna::from_slice(&seq[start..end]),
+            Location::Between(pos) => {
+                assert!(pos <= seq.len(), "site {} is out of sequence bounds", pos);
+                Dna::new(Vec::new())
+            },
+            Location::Complement(ref loc) => loc.extract(seq).reverse_complement(),
+            Location::Join(ref locs) |
+            Location::Order(ref locs) => {
+                let vec = locs.iter()
+                    .flat_map(|l| l.extract(seq).into_iter())
+                    .collect();
+                Dna::new(vec)
+            },
+        }
+    }
+
+}
+
+impl str::FromStr for Location {
+    type Err = String;
+
+    /// Parses location in the GenBank notation, e.g.
+    /// `complement(join(<1..200,300..>400))`.
+    fn from_str(s: &str) -> ::std::result::Result<Location, String> {
+        let s: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+        parse_location(&s)
+    }
+}
+
+fn parse_location(s: &str) -> ::std::result::Result<Location, String> {
+    if let Some(inner) = strip_operator(s, "complement") {
+        return Ok(Location::Complement(Box::new(parse_location(inner)?)));
+    }
+    for &(op, variant) in [("join", Location::Join as fn(Vec<Location>) -> Location), ("order", Location::Order)].iter() {
+        if let Some(inner) = strip_operator(s, op) {
+            let locs = split_top_level(inner).into_iter()
+                .map(parse_location)
+                .collect::<::std::result::Result<_, _>>()?;
+            return Ok(variant(locs));
+        }
+    }
+    if s.contains(':') {
+        return Err(format!("remote location '{}' is not supported", s));
+    }
+
+    let parse_position = |p: &str| -> ::std::result::Result<(usize, bool), String> {
+        let partial = p.starts_with('<') || p.starts_with('>');
+        let digits = p.trim_start_matches(&['<', '>'][..]);
+        match digits.parse::<usize>() {
+            Ok(n) if n > 0 => Ok((n, partial)),
+            _ => Err(format!("invalid location '{}'", s)),
+        }
+    };
+
+    if let Some(i) = s.find('^') {
+        // the second base follows the first one, or is the first base of
+        // a circular sequence
+        let (left, _) = parse_position(&s[..i])?;
+        let (right, _) = parse_position(&s[i + 1..])?;
+        if right != left + 1 && right != 1 {
+            return Err(format!("invalid location '{}'", s));
+        }
+        return Ok(Location::Between(left));
+    }
+
+    let (start, end) = if let Some(i) = s.find("..") {
+        (&s[..i], &s[i + 2..])
+    } else if let Some(i) = s.find('.') {
+        (&s[..i], &s[i + 1..])
+    } else {
+        (s, s)
+    };
+    let (start, partial_start) = parse_position(start)?;
+    let (end, partial_end) = parse_position(end)?;
+    if start > end {
+        return Err(format!("invalid location '{}'", s));
+    }
+    Ok(Location::Range { start: start - 1, end, partial_start, partial_end })
+}
+
+/// Returns arguments of `op(...)`.
+fn strip_operator<'a>(s: &'a str, op: &str) -> Option<&'a str> {
+    s.strip_prefix(op)
+        .and_then(|rest| rest.strip_prefix('('))
+        .and_then(|rest| rest.strip_suffix(')'))
+}
+
+/// Splits comma separated list, ignoring commas in nested parentheses.
+fn split_top_level(s: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut from = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&s[from..i]);
+                from = i + 1;
+            },
+            _ => (),
+        }
+    }
+    parts.push(&s[from..]);
+    parts
+}
+
+/// Feature table entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Feature {
+    pub kind: FeatureKind,
+    pub location: Location,
+    /// Qualifiers in the file order, values are unquoted.
+    pub qualifiers: Vec<(String, Option<String>)>,
+}
+
+impl Feature {
+
+    /// Value of the first qualifier with the given `key`.
+    pub fn qualifier(&self, key: &str) -> Option<&str> {
+        self.qualifiers.iter()
+            .find(|&(k, _)| k == key)
+            .and_then(|(_, v)| v.as_ref().map(|v| v.as_str()))
+    }
+
+}
+
+/// GenBank record.
+///
+/// # Examples
+///
+/// ```
+/// use bio::data::genbank::{FeatureKind, Reader};
+///
+/// let input = b"\
+/// LOCUS       TOY                       24 bp    DNA     circular BCT 01-JAN-2016
+/// DEFINITION  Toy chromosome.
+/// ACCESSION   TOY001
+/// FEATURES             Location/Qualifiers
+///      gene            complement(3..8)
+///                      /gene=\"dnaA\"
+///      CDS             join(1..3,10..12)
+///                      /product=\"toy
+///                      protein\"
+/// ORIGIN
+///         1 aattatccac aggatccaat tcga
+/// //
+/// ";
+/// let record = Reader::new(&input[..]).next().unwrap().unwrap();
+/// let dnaa = record.gene("dnaA").unwrap();
+///
+/// assert_eq!(record.name, "TOY");
+/// assert_eq!(dnaa.location.start(), 2);
+/// assert_eq!(dnaa.location.extract(&record.seq).as_str(), "GGATAA");
+/// assert_eq!(record.features[1].kind, FeatureKind::Cds);
+/// assert_eq!(record.features[1].qualifier("product"), Some("toy protein"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    /// Locus name.
+    pub name: String,
+    pub definition: String,
+    pub accession: String,
+    pub version: String,
+    pub features: Vec<Feature>,
+    pub seq: Dna,
+}
+
+impl Record {
+
+    fn new() -> Record {
+        Record {
+            name: String::new(),
+            definition: String::new(),
+            accession: String::new(),
+            version: String::new(),
+            features: Vec::new(),
+            seq: Dna::new(Vec::new()),
+        }
+    }
+
+    /// Features of the given `kind`.
+    pub fn features<'a>(&'a self, kind: &'a FeatureKind) -> Box<dyn Iterator<Item = &'a Feature> + 'a> {
+        Box::new(self.features.iter().filter(move |f| f.kind == *kind))
+    }
+
+    /// The `gene` feature with `/gene` qualifier equal to `name`.
+    pub fn gene(&self, name: &str) -> Option<&Feature> {
+        self.features(&FeatureKind::Gene)
+            .find(|f| f.qualifier("gene") == Some(name))
+    }
+
+}
+
+/// Feature being read, location and qualifiers may span several lines.
+struct PartialFeature {
+    key: String,
+    line: usize,
+    location: String,
+    qualifiers: Vec<(String, Option<String>)>,
+}
+
+/// Iterator over records of a GenBank file.
+pub struct Reader<R> {
+    lines: Lines<R>,
+}
+
+impl<R: BufRead> Reader<R> {
+
+    pub fn new(reader: R) -> Reader<R> {
+        Reader { lines: Lines::new(reader) }
+    }
+
+    fn read_record(&mut self) -> Result<Option<Record>> {
+        loop {
+            if !self.lines.read_line()? {
+                return Ok(None);
+            }
+            if !self.lines.line.trim().is_empty() {
+                break;
+            }
+        }
+        if !self.lines.line.starts_with("LOCUS") {
+            return Err(self.lines.format_error("expected LOCUS line"));
+        }
+
+        let mut record = Record::new();
+        record.name = self.lines.line[5..].split_whitespace().next().unwrap_or("").to_owned();
+        let mut keyword = String::new();
+
+        while self.lines.read_line()? {
+            let line = self.lines.line.trim_end();
+            if line.starts_with("//") {
+                return Ok(Some(record));
+            }
+            if line.starts_with(' ') {
+                // header continuation line
+                let value = line.trim();
+                if keyword == "DEFINITION" {
+                    record.definition.push(' ');
+                    record.definition.push_str(value);
+                }
+                continue;
+            }
+            let (key, value) = match line.find(' ') {
+                Some(i) => (&line[..i], line[i..].trim()),
+                None => (line, ""),
+            };
+            keyword = key.to_owned();
+            match key {
+                "DEFINITION" => record.definition = value.to_owned(),
+                "ACCESSION" => record.accession = value.split_whitespace().next().unwrap_or("").to_owned(),
+                "VERSION" => record.version = value.split_whitespace().next().unwrap_or("").to_owned(),
+                "FEATURES" => {
+                    if self.read_features(&mut record.features)? {
+                        record.seq = self.read_origin()?;
+                    }
+                    return Ok(Some(record));
+                },
+                "ORIGIN" => {
+                    record.seq = self.read_origin()?;
+                    return Ok(Some(record));
+                },
+                _ => (),
+            }
+        }
+        Err(self.lines.format_error("unexpected end of input, expected '//'"))
+    }
+
+    /// Reads feature table and the keywords after it up to ORIGIN or the
+    /// `//` terminator. Returns `true` if ORIGIN follows.
+    fn read_features(&mut self, features: &mut Vec<Feature>) -> Result<bool> {
+        let mut feature: Option<PartialFeature> = None;
+        let mut in_table = true;
+        loop {
+            if !self.lines.read_line()? {
+                return Err(self.lines.format_error("unexpected end of input, expected '//'"));
+            }
+            let line = self.lines.line.trim_end().to_owned();
+            if !line.starts_with(' ') {
+                if let Some(f) = feature.take() {
+                    features.push(self.finish_feature(f)?);
+                }
+                if line.starts_with("//") {
+                    return Ok(false);
+                }
+                if line.starts_with("ORIGIN") {
+                    return Ok(true);
+                }
+                // other keywords, like BASE COUNT or CONTIG, are skipped
+                in_table = false;
+                continue;
+            }
+            if !in_table {
+                continue;
+            }
+
+            let indent = line.len() - line.trim_start().len();
+            if indent < QUALIFIER_COLUMN {
+                if let Some(f) = feature.take() {
+                    features.push(self.finish_feature(f)?);
+                }
+                let mut parts = line.split_whitespace();
+                let key = parts.next().unwrap_or("").to_owned();
+                let location = parts.collect::<Vec<_>>().concat();
+                feature = Some(PartialFeature {
+                    key,
+                    line: self.lines.line_no,
+                    location,
+                    qualifiers: Vec::new(),
+                });
+                continue;
+            }
+
+            let f = match feature {
+                Some(ref mut f) => f,
+                None => return Err(self.lines.format_error("qualifier outside of a feature")),
+            };
+            let content = line.trim();
+            let quote_open = match f.qualifiers.last() {
+                Some(&(_, Some(ref value))) => is_quote_open(value),
+                _ => false,
+            };
+            if let (Some(qualifier), false) = (content.strip_prefix('/'), quote_open) {
+                let (key, value) = match qualifier.find('=') {
+                    Some(i) => (&qualifier[..i], Some(qualifier[i + 1..].to_owned())),
+                    None => (qualifier, None),
+                };
+                f.qualifiers.push((key.to_owned(), value));
+            } else if f.qualifiers.is_empty() {
+                f.location.push_str(content);
+            } else if let Some(&mut (ref key, Some(ref mut value))) = f.qualifiers.last_mut() {
+                if key != "translation" {
+                    value.push(' ');
+                }
+                value.push_str(content);
+            }
+        }
+    }
+
+    fn finish_feature(&self, f: PartialFeature) -> Result<Feature> {
+        let location = f.location.parse::<Location>()
+            .map_err(|message| self.lines.error(super::Error::format(f.line, &message)))?;
+        let qualifiers = f.qualifiers.into_iter()
+            .map(|(k, v)| (k, v.map(|v| unquote(&v))))
+            .collect();
+        Ok(Feature {
+            kind: FeatureKind::from_key(&f.key),
+            location,
+            qualifiers,
+        })
+    }
+
+    /// Reads sequence lines after ORIGIN until the `//` terminator.
+    fn read_origin(&mut self) -> Result<Dna> {
+        let mut seq = Vec::new();
+        loop {
+            self.lines.expect_line("'//'")?;
+            let line = &self.lines.line;
+            if line.starts_with("//") {
+                break;
+            }
+            seq.extend(line.bytes()
+                .filter(|b| b.is_ascii_alphabetic())
+                .map(|b| b.to_ascii_uppercase()));
+        }
+        Ok(Dna::new(seq))
+    }
+
+}
+
+impl Reader<Box<dyn BufRead>> {
+
+    /// Opens GenBank file at `path`, possibly gzip compressed.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Reader<Box<dyn BufRead>>> {
+        let path = path.as_ref();
+        let mut reader = Reader::new(super::open(path)?);
+        reader.lines.path = Some(path.to_path_buf());
+        Ok(reader)
+    }
+
+}
+
+impl<R: BufRead> Iterator for Reader<R> {
+    type Item = Result<Record>;
+
+    fn next(&mut self) -> Option<Result<Record>> {
+        match self.read_record() {
+            Ok(Some(record)) => Some(Ok(record)),
+            Ok(None) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+/// Returns `true` if the qualifier `value` starts a quoted string which is
+/// not closed yet, quotes inside values are escaped as `""`.
+fn is_quote_open(value: &str) -> bool {
+    value.starts_with('"') && value.matches('"').count() % 2 == 1
+}
+
+/// Strips the quotes around a quoted qualifier value and collapses the
+/// escaped `""` inside it to `"`, other values are returned unchanged.
+fn unquote(s: &str) -> String {
+    match s.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+        Some(inner) => inner.replace("\"\"", "\""),
+        None => s.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{FeatureKind, Location, Reader};
+
+    fn range(start: usize, end: usize) -> Location {
+        Location::Range { start, end, partial_start: false, partial_end: false }
+    }
+
+    #[test]
+    fn test_parse_location() {
+        assert_eq!("467".parse::<Location>(), Ok(range(466, 467)));
+        assert_eq!("340..565".parse::<Location>(), Ok(range(339, 565)));
+        assert_eq!("<345..>500".parse::<Location>(), Ok(Location::Range {
+            start: 344, end: 500, partial_start: true, partial_end: true,
+        }));
+        assert_eq!("complement(join(2691..4571, 4918..5163))".parse::<Location>(),
+                   Ok(Location::Complement(Box::new(Location::Join(vec![range(2690, 4571), range(4917, 5163)])))));
+        assert_eq!("123^124".parse::<Location>(), Ok(Location::Between(123)));
+        assert_eq!("order(1..2,5..6)".parse::<Location>(), Ok(Location::Order(vec![range(0, 2), range(4, 6)])));
+        assert!("123^125".parse::<Location>().is_err());
+        assert!("J00194.1:100..202".parse::<Location>().is_err());
+        assert!("join(1..2,x)".parse::<Location>().is_err());
+    }
+
+    #[test]
+    fn test_location_extract() {
+        let seq = b"AACCGGTTAC";
+        let loc: Location = "join(1..2,complement(5..7))".parse().unwrap();
+        assert_eq!(loc.extract(seq).as_str(), "AAACC");
+        assert_eq!((loc.start(), loc.end()), (0, 7));
+        let loc: Location = "complement(join(1..2,9..10))".parse().unwrap();
+        assert!(loc.is_complement());
+        assert_eq!(loc.extract(seq).as_str(), "GTTT");
+    }
+
+    #[test]
+    fn test_multiple_records() {
+        let input = b"\
+LOCUS       R1                        8 bp    DNA     linear
+DEFINITION  First record,
+            two lines.
+VERSION     R1.1
+FEATURES             Location/Qualifiers
+     rRNA            join(1..2,
+                     5..6)
+                     /pseudo
+                     /note=\"split
+                     /between\"
+                     /note=\"\"\"quoted\"\"
+                     /after\"
+BASE COUNT        2 a      2 c      2 g      2 t
+ORIGIN
+        1 acgtacgt
+//
+LOCUS       R2                        4 bp    DNA     linear
+ORIGIN
+        1 ttga
+//
+";
+        let records: Vec<_> = Reader::new(&input[..]).map(|r| r.unwrap()).collect();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].definition, "First record, two lines.");
+        assert_eq!(records[0].version, "R1.1");
+        assert_eq!(records[0].seq.as_str(), "ACGTACGT");
+        let rrna = &records[0].features[0];
+        assert_eq!(rrna.kind, FeatureKind::RRna);
+        assert_eq!(rrna.qualifiers[0], ("pseudo".to_owned(), None));
+        assert_eq!(rrna.qualifier("note"), Some("split /between"));
+        assert_eq!(rrna.qualifiers.len(), 3);
+        assert_eq!(rrna.qualifiers[2].1, Some("\"quoted\" /after".to_owned()));
+        assert_eq!(rrna.location.extract(&records[0].seq).as_str(), "ACAC");
+        assert_eq!(records[1].name, "R2");
+        assert_eq!(records[1].seq.as_str(), "TTGA");
+    }
+
+    #[test]
+    fn test_invalid_location_line() {
+        let input = b"\
+LOCUS       R1
+FEATURES             Location/Qualifiers
+     gene            1..4
+     gene            4..x
+//
+";
+        let err = Reader::new(&input[..]).next().unwrap().unwrap_err();
+        assert_eq!(err.line(), Some(4));
+    }
+
+}
@@ -0,0 +1,329 @@
+//!
+//! GFF3 format reader and writer
+//!
+//! GFF3 positions are 1-based and inclusive, they are converted to and
+//! from the 0-based half-open positions of `Interval`.
+//!
+
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+use super::{Lines, Result};
+use super::interval::{Interval, Strand};
+
+/// GFF3 feature line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    /// Location, name, score and strand of the feature. The name is taken
+    /// from the `Name` attribute, or from `ID` if there is no name.
+    pub interval: Interval,
+    /// Program or database that produced the feature.
+    pub source: String,
+    /// Feature type, a Sequence Ontology term such as `gene` or
+    /// `binding_site`.
+    pub kind: String,
+    pub phase: Option<u8>,
+    /// Attributes in the file order, percent-encoding is decoded.
+    pub attributes: Vec<(String, String)>,
+}
+
+impl Record {
+
+    pub fn new(interval: Interval, source: &str, kind: &str) -> Record {
+        Record {
+            interval,
+            source: source.to_owned(),
+            kind: kind.to_owned(),
+            phase: None,
+            attributes: Vec::new(),
+        }
+    }
+
+    /// Value of the attribute with the given `key`.
+    pub fn attribute(&self, key: &str) -> Option<&str> {
+        self.attributes.iter()
+            .find(|&(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+}
+
+/// Iterator over feature lines of a GFF3 file. Directives and comments
+/// are skipped, reading stops at the `##FASTA` section.
+///
+/// # Examples
+///
+/// ```
+/// use bio::data::gff::Reader;
+///
+/// let input = b"##gff-version 3\nchr1\tbio\tbinding_site\t11\t19\t.\t+\t.\tID=box1;Name=DnaA%20box\n";
+/// let record = Reader::new(&input[..]).next().unwrap().unwrap();
+///
+/// assert_eq!(record.kind, "binding_site");
+/// assert_eq!((record.interval.start, record.interval.end), (10, 19));
+/// assert_eq!(record.interval.name, Some("DnaA box".to_owned()));
+/// ```
+pub struct Reader<R> {
+    lines: Lines<R>,
+    done: bool,
+}
+
+impl<R: BufRead> Reader<R> {
+
+    pub fn new(reader: R) -> Reader<R> {
+        Reader { lines: Lines::new(reader), done: false }
+    }
+
+    fn read_record(&mut self) -> Result<Option<Record>> {
+        loop {
+            if self.done || !self.lines.read_line()? {
+                return Ok(None);
+            }
+            let line = self.lines.line.trim();
+            if line.starts_with("##FASTA") || line.starts_with('>') {
+                self.done = true;
+                return Ok(None);
+            }
+            if !(line.is_empty() || line.starts_with('#')) {
+                break;
+            }
+        }
+
+        let fields: Vec<_> = self.lines.line.trim_end().split('\t').collect();
+        if fields.len() != 9 {
+            return Err(self.lines.format_error("expected 9 tab separated columns"));
+        }
+        let start = fields[3].parse::<usize>().ok()
+            .filter(|&s| s > 0)
+            .ok_or_else(|| self.lines.format_error("invalid start position"))?;
+        let end = fields[4].parse::<usize>()
+            .map_err(|_| self.lines.format_error("invalid end position"))?;
+        if start > end + 1 {
+            return Err(self.lines.format_error("start position is past the end"));
+        }
+
+        let mut interval = Interval::new(&decode(fields[0]), start - 1, end);
+        if fields[5] != "." {
+            let score = fields[5].parse::<f64>()
+                .map_err(|_| self.lines.format_error("invalid score"))?;
+            interval.score = Some(score);
+        }
+        interval.strand = Strand::from_symbol(fields[6])
+            .ok_or_else(|| self.lines.format_error("invalid strand"))?;
+
+        let mut record = Record::new(interval, &decode(fields[1]), &decode(fields[2]));
+        if fields[7] != "." {
+            let phase = fields[7].parse::<u8>().ok()
+                .filter(|&p| p < 3)
+                .ok_or_else(|| self.lines.format_error("invalid phase"))?;
+            record.phase = Some(phase);
+        }
+        if fields[8] != "." {
+            for attr in fields[8].split(';').filter(|a| !a.trim().is_empty()) {
+                match attr.find('=') {
+                    Some(i) => record.attributes.push((decode(attr[..i].trim()), decode(&attr[i + 1..]))),
+                    None => return Err(self.lines.format_error("attribute without value")),
+                }
+            }
+        }
+        record.interval.name = record.attribute("Name")
+            .or_else(|| record.attribute("ID"))
+            .map(|n| n.to_owned());
+        Ok(Some(record))
+    }
+
+}
+
+impl Reader<Box<dyn BufRead>> {
+
+    /// Opens GFF3 file at `path`, possibly gzip compressed.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Reader<Box<dyn BufRead>>> {
+        let path = path.as_ref();
+        let mut reader = Reader::new(super::open(path)?);
+        reader.lines.path = Some(path.to_path_buf());
+        Ok(reader)
+    }
+
+}
+
+impl<R: BufRead> Iterator for Reader<R> {
+    type Item = Result<Record>;
+
+    fn next(&mut self) -> Option<Result<Record>> {
+        match self.read_record() {
+            Ok(Some(record)) => Some(Ok(record)),
+            Ok(None) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+/// Writes GFF3 feature lines, starting with the `##gff-version 3`
+/// directive.
+///
+/// # Examples
+///
+/// ```
+/// use bio::data::gff::Writer;
+/// use bio::data::interval::{Interval, Strand};
+///
+/// let hits = Interval::from_hits("NC_003198", &[12], 9, "DnaA_box", Strand::Reverse);
+/// let mut out = Vec::new();
+/// Writer::new(&mut out, "bio", "binding_site").write_intervals(&hits).unwrap();
+///
+/// assert_eq!(String::from_utf8(out).unwrap(),
+///            "##gff-version 3\nNC_003198\tbio\tbinding_site\t13\t21\t.\t-\t.\tName=DnaA_box\n");
+/// ```
+pub struct Writer<W> {
+    writer: W,
+    source: String,
+    kind: String,
+    header: bool,
+}
+
+impl<W: Write> Writer<W> {
+
+    /// Creates writer, `source` and `kind` are used for the intervals
+    /// written with `write_interval`.
+    pub fn new(writer: W, source: &str, kind: &str) -> Writer<W> {
+        Writer {
+            writer,
+            source: source.to_owned(),
+            kind: kind.to_owned(),
+            header: false,
+        }
+    }
+
+    fn write_header(&mut self) -> io::Result<()> {
+        if !self.header {
+            self.header = true;
+            writeln!(self.writer, "##gff-version 3")?;
+        }
+        Ok(())
+    }
+
+    pub fn write(&mut self, record: &Record) -> io::Result<()> {
+        self.write_header()?;
+        let interval = &record.interval;
+        let score = interval.score.map_or(".".to_owned(), |s| s.to_string());
+        let phase = record.phase.map_or(".".to_owned(), |p| p.to_string());
+
+        let mut attributes: Vec<_> = record.attributes.iter()
+            .map(|(k, v)| format!("{}={}", encode(k), encode(v)))
+            .collect();
+        if let Some(ref name) = interval.name {
+            if record.attribute("Name").is_none() && record.attribute("ID") != Some(name) {
+                attributes.push(format!("Name={}", encode(name)));
+            }
+        }
+        let attributes = if attributes.is_empty() { ".".to_owned() } else { attributes.join(";") };
+
+        writeln!(self.writer, "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+                 encode(&interval.chrom), encode(&record.source), encode(&record.kind),
+                 interval.start + 1, interval.end, score, interval.strand, phase, attributes)
+    }
+
+    /// Writes interval with the writer's source and feature type.
+    pub fn write_interval(&mut self, interval: &Interval) -> io::Result<()> {
+        let record = Record::new(interval.clone(), &self.source, &self.kind);
+        self.write(&record)
+    }
+
+    pub fn write_intervals(&mut self, intervals: &[Interval]) -> io::Result<()> {
+        for interval in intervals.iter() {
+            self.write_interval(interval)?;
+        }
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+}
+
+/// Percent-encodes characters reserved in GFF3 columns.
+fn encode(s: &str) -> String {
+    let mut res = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            ';' | '=' | '&' | ',' | '%' | '\t' | '\n' | '\r' => res.push_str(&format!("%{:02X}", c as u8)),
+            c => res.push(c),
+        }
+    }
+    res
+}
+
+/// Decodes percent-encoded characters, malformed escapes are kept as is.
+fn decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut res = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let escaped = s.get(i + 1..i + 3).and_then(|h| u8::from_str_radix(h, 16).ok());
+            if let Some(b) = escaped {
+                res.push(b);
+                i += 3;
+                continue;
+            }
+        }
+        res.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&res).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{Reader, Record, Writer, decode, encode};
+    use data::interval::{Interval, Strand};
+
+    #[test]
+    fn test_encoding() {
+        assert_eq!(encode("a;b=c%"), "a%3Bb%3Dc%25");
+        assert_eq!(decode("a%3Bb%3Dc%25"), "a;b=c%");
+        assert_eq!(decode("100%"), "100%");
+        assert_eq!(decode("%zz"), "%zz");
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let mut interval = Interval::new("chr1", 99, 200);
+        interval.score = Some(0.5);
+        interval.strand = Strand::Forward;
+        interval.name = Some("gene;1".to_owned());
+        let mut record = Record::new(interval, "bio", "gene");
+        record.phase = Some(0);
+        record.attributes.push(("ID".to_owned(), "g1".to_owned()));
+        record.attributes.push(("Name".to_owned(), "gene;1".to_owned()));
+
+        let mut out = Vec::new();
+        {
+            let mut writer = Writer::new(&mut out, "bio", "gene");
+            writer.write(&record).unwrap();
+            writer.write_interval(&Interval::new("chr2", 0, 1)).unwrap();
+        }
+        let read: Vec<_> = Reader::new(&out[..]).map(|r| r.unwrap()).collect();
+        assert_eq!(read.len(), 2);
+        assert_eq!(read[0], record);
+        assert_eq!(read[1].interval, Interval::new("chr2", 0, 1));
+    }
+
+    #[test]
+    fn test_fasta_section() {
+        let input = b"chr1\t.\tgene\t1\t9\t.\t.\t.\t.\n##FASTA\n>chr1\nACGTACGTA\n";
+        let records: Vec<_> = Reader::new(&input[..]).map(|r| r.unwrap()).collect();
+        assert_eq!(records.len(), 1);
+        assert!(records[0].attributes.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_line() {
+        let input = b"##gff-version 3\nchr1\t.\tgene\t0\t9\t.\t.\t.\t.\n";
+        let err = Reader::new(&input[..]).next().unwrap().unwrap_err();
+        assert_eq!(err.line(), Some(2));
+    }
+
+}
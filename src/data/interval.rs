@@ -0,0 +1,146 @@
+//!
+//! Genomic intervals shared by the annotation formats
+//!
+
+use std::fmt;
+use std::ops::Range;
+
+use ::dna::{self, Dna};
+
+/// Strand of a genomic interval.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Strand {
+    Forward,
+    Reverse,
+    Unknown,
+}
+
+impl Strand {
+
+    /// Parses `+`, `-` or `.` strand notation.
+    pub fn from_symbol(s: &str) -> Option<Strand> {
+        match s {
+            "+" => Some(Strand::Forward),
+            "-" => Some(Strand::Reverse),
+            "." | "?" => Some(Strand::Unknown),
+            _ => None,
+        }
+    }
+
+    pub fn symbol(self) -> char {
+        match self {
+            Strand::Forward => '+',
+            Strand::Reverse => '-',
+            Strand::Unknown => '.',
+        }
+    }
+
+}
+
+impl fmt::Display for Strand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+/// Named interval on a sequence. Positions are 0-based, `end` is
+/// exclusive, as returned by `seq::find`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Interval {
+    /// Name of the sequence (chromosome, contig) the interval is on.
+    pub chrom: String,
+    pub start: usize,
+    pub end: usize,
+    pub name: Option<String>,
+    pub score: Option<f64>,
+    pub strand: Strand,
+}
+
+impl Interval {
+
+    pub fn new(chrom: &str, start: usize, end: usize) -> Interval {
+        assert!(start <= end, "interval start is past its end");
+        Interval {
+            chrom: chrom.to_owned(),
+            start,
+            end,
+            name: None,
+            score: None,
+            strand: Strand::Unknown,
+        }
+    }
+
+    /// Intervals of length `len` starting at `positions`, such as hits of
+    /// `seq::find`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bio::data::interval::{Interval, Strand};
+    /// use bio::seq;
+    ///
+    /// let genome = b"TTATCCACATTATCCACA";
+    /// let hits = seq::find(genome, b"TTATCCACA");
+    /// let intervals = Interval::from_hits("chr", &hits, 9, "DnaA_box", Strand::Forward);
+    ///
+    /// assert_eq!(intervals[1].start, 9);
+    /// assert_eq!(intervals[1].end, 18);
+    /// ```
+    pub fn from_hits(chrom: &str, positions: &[usize], len: usize, name: &str, strand: Strand) -> Vec<Interval> {
+        positions.iter()
+            .map(|&i| Interval {
+                name: Some(name.to_owned()),
+                strand,
+                ..Interval::new(chrom, i, i + len)
+            })
+            .collect()
+    }
+
+    /// Intervals of clump regions named by their k-mers, such as those
+    /// of `strings::clump_regions`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bio::data::interval::Interval;
+    /// use bio::strings;
+    ///
+    /// let genome = "CTACGAACGTCAGTCCATGGAACGCACG";
+    /// let clumps = Interval::from_clumps("chr", &strings::clump_regions(genome, 3, 8, 2));
+    ///
+    /// assert_eq!(clumps[0].name, Some("ACG".to_owned()));
+    /// assert_eq!((clumps[0].start, clumps[0].end), (2, 9));
+    /// ```
+    pub fn from_clumps(chrom: &str, clumps: &[(String, Range<usize>)]) -> Vec<Interval> {
+        clumps.iter()
+            .map(|(kmer, region)| Interval {
+                name: Some(kmer.clone()),
+                ..Interval::new(chrom, region.start, region.end)
+            })
+            .collect()
+    }
+
+    /// Forward strand intervals of `motifs` found by the motif searches of
+    /// `dna`, such as `dna::greedy_motif_search`, in DNA strings `dnas`
+    /// named by `chroms`. Motifs which do not occur in their strings are
+    /// skipped, see `dna::motif_positions`.
+    pub fn from_motifs(chroms: &[&str], dnas: &[Dna], motifs: &[Dna], name: &str) -> Vec<Interval> {
+        dna::motif_positions(dnas, motifs).into_iter()
+            .zip(chroms.iter().zip(motifs.iter()))
+            .filter_map(|(pos, (chrom, motif))| pos.map(|i| Interval {
+                name: Some(name.to_owned()),
+                strand: Strand::Forward,
+                ..Interval::new(chrom, i, i + motif.len())
+            }))
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+}
@@ -0,0 +1,211 @@
+//!
+//! JASPAR count matrix format reader and writer
+//!
+//! Reads both the current format, with `A  [ 1 2 ]` rows, and the older
+//! one with bare rows of counts in `A`, `C`, `G`, `T` order.
+//!
+
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+use super::{Lines, Result, split_header};
+use super::motif::Motif;
+
+/// Iterator over count matrices of a JASPAR file.
+///
+/// # Examples
+///
+/// ```
+/// use bio::data::jaspar::Reader;
+///
+/// let input = b">MA0001.1 AGL3\nA  [ 0  3 ]\nC  [94 75 ]\nG  [ 1  0 ]\nT  [ 2 19 ]\n";
+/// let motif = Reader::new(&input[..]).next().unwrap().unwrap();
+///
+/// assert_eq!(motif.id, "MA0001.1");
+/// assert_eq!(motif.name, Some("AGL3".to_owned()));
+/// assert_eq!(motif.sites, Some(97));
+/// assert_eq!(motif.counts()[1], [3., 75., 0., 19.]);
+/// ```
+pub struct Reader<R> {
+    lines: Lines<R>,
+}
+
+impl<R: BufRead> Reader<R> {
+
+    pub fn new(reader: R) -> Reader<R> {
+        Reader { lines: Lines::new(reader) }
+    }
+
+    /// Reads next non-blank line, returns `false` at the end of input.
+    fn read_nonblank(&mut self) -> Result<bool> {
+        while self.lines.read_line()? {
+            if !self.lines.line.trim().is_empty() {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    fn read_motif(&mut self) -> Result<Option<Motif>> {
+        if !self.read_nonblank()? {
+            return Ok(None);
+        }
+        let (id, name) = match self.lines.line.trim().strip_prefix('>') {
+            Some(header) => {
+                let (id, name) = split_header(header);
+                (id.to_owned(), name.map(|n| n.to_owned()))
+            },
+            None => return Err(self.lines.format_error("expected JASPAR header starting with '>'")),
+        };
+
+        let mut rows: [Option<Vec<f64>>; 4] = [None, None, None, None];
+        for i in 0..4 {
+            if !self.read_nonblank()? {
+                self.lines.expect_line("count matrix row")?;
+            }
+            let line = self.lines.line.trim();
+            let (row, counts) = match line.chars().next() {
+                Some(c) if c.is_ascii_alphabetic() => match "ACGT".find(c.to_ascii_uppercase()) {
+                    Some(row) => (row, &line[1..]),
+                    None => return Err(self.lines.format_error("unknown nucleotide")),
+                },
+                _ => (i, line),
+            };
+            let counts = counts.trim().trim_start_matches('[').trim_end_matches(']');
+            let counts = counts.split_whitespace()
+                .map(|n| n.parse::<f64>())
+                .collect::<::std::result::Result<Vec<_>, _>>()
+                .map_err(|_| self.lines.format_error("invalid count"))?;
+            if rows[row].is_some() {
+                return Err(self.lines.format_error("duplicate count matrix row"));
+            }
+            if rows.iter().flatten().any(|r| r.len() != counts.len()) {
+                return Err(self.lines.format_error("count matrix rows differ in length"));
+            }
+            rows[row] = Some(counts);
+        }
+
+        let rows: Vec<_> = rows.iter().flatten().collect();
+        let counts: Vec<_> = (0..rows[0].len())
+            .map(|j| [rows[0][j], rows[1][j], rows[2][j], rows[3][j]])
+            .collect();
+        let mut motif = Motif::from_counts(&id, &counts);
+        motif.name = name;
+        Ok(Some(motif))
+    }
+
+}
+
+impl Reader<Box<dyn BufRead>> {
+
+    /// Opens JASPAR file at `path`, possibly gzip compressed.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Reader<Box<dyn BufRead>>> {
+        let path = path.as_ref();
+        let mut reader = Reader::new(super::open(path)?);
+        reader.lines.path = Some(path.to_path_buf());
+        Ok(reader)
+    }
+
+}
+
+impl<R: BufRead> Iterator for Reader<R> {
+    type Item = Result<Motif>;
+
+    fn next(&mut self) -> Option<Result<Motif>> {
+        match self.read_motif() {
+            Ok(Some(motif)) => Some(Ok(motif)),
+            Ok(None) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+/// Writes motifs as JASPAR count matrices, see `Motif::counts`.
+///
+/// # Examples
+///
+/// ```
+/// use bio::data::jaspar::Writer;
+/// use bio::data::motif::Motif;
+/// use bio::dna::Dna;
+///
+/// let motif = Motif::from_sites("m1", &[Dna::from_str("AC"), Dna::from_str("AG")]);
+/// let mut out = Vec::new();
+/// Writer::new(&mut out).write(&motif).unwrap();
+///
+/// assert_eq!(String::from_utf8(out).unwrap(),
+///            ">m1\nA  [   2   0 ]\nC  [   0   1 ]\nG  [   0   1 ]\nT  [   0   0 ]\n");
+/// ```
+pub struct Writer<W> {
+    writer: W,
+}
+
+impl<W: Write> Writer<W> {
+
+    pub fn new(writer: W) -> Writer<W> {
+        Writer { writer }
+    }
+
+    pub fn write(&mut self, motif: &Motif) -> io::Result<()> {
+        match motif.name {
+            Some(ref name) => writeln!(self.writer, ">{} {}", motif.id, name)?,
+            None => writeln!(self.writer, ">{}", motif.id)?,
+        }
+        let counts = motif.counts();
+        for (i, nuc) in "ACGT".chars().enumerate() {
+            write!(self.writer, "{}  [", nuc)?;
+            for col in counts.iter() {
+                write!(self.writer, " {:>3}", col[i])?;
+            }
+            writeln!(self.writer, " ]")?;
+        }
+        Ok(())
+    }
+
+    pub fn write_all(&mut self, motifs: &[Motif]) -> io::Result<()> {
+        for motif in motifs.iter() {
+            self.write(motif)?;
+        }
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{Reader, Writer};
+    use data::motif::Motif;
+
+    #[test]
+    fn test_roundtrip() {
+        let mut m1 = Motif::from_counts("MA0001.1", &[[0., 94., 1., 2.], [3., 75., 0., 19.]]);
+        m1.name = Some("AGL3".to_owned());
+        let m2 = Motif::from_counts("MA0002.1", &[[5., 5., 0., 0.]]);
+        let motifs = vec![m1, m2];
+
+        let mut out = Vec::new();
+        Writer::new(&mut out).write_all(&motifs).unwrap();
+        let read: Vec<_> = Reader::new(&out[..]).map(|m| m.unwrap()).collect();
+        assert_eq!(read, motifs);
+    }
+
+    #[test]
+    fn test_bare_rows() {
+        let input = b">MA0004.1 Arnt\n4 19 0\n16 0 20\n0 1 0\n0 0 0\n";
+        let motif = Reader::new(&input[..]).next().unwrap().unwrap();
+        assert_eq!(motif.counts(), [[4., 16., 0., 0.], [19., 0., 1., 0.], [0., 20., 0., 0.]]);
+    }
+
+    #[test]
+    fn test_truncated_matrix() {
+        let input = b">MA0001.1\nA [ 1 2 ]\nC [ 1 2 ]\n";
+        let err = Reader::new(&input[..]).next().unwrap().unwrap_err();
+        assert_eq!(err.line(), Some(4));
+    }
+
+}
@@ -0,0 +1,270 @@
+//!
+//! MEME minimal motif format reader and writer
+//!
+//! Only DNA motifs are supported, the letter-probability matrix columns
+//! are `A`, `C`, `G` and `T`.
+//!
+
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+use ::dna::Profile;
+use super::{Lines, Result};
+use super::motif::Motif;
+
+/// Iterator over motifs of a MEME file.
+///
+/// # Examples
+///
+/// ```
+/// use bio::data::meme::Reader;
+///
+/// let input = b"MEME version 4\n\nALPHABET= ACGT\n\n\
+///               MOTIF m1 DnaA\nletter-probability matrix: alength= 4 w= 2 nsites= 4\n\
+///               0.5 0.0 0.5 0.0\n0.0 0.0 0.0 1.0\n";
+/// let motif = Reader::new(&input[..]).next().unwrap().unwrap();
+///
+/// assert_eq!(motif.id, "m1");
+/// assert_eq!(motif.name, Some("DnaA".to_owned()));
+/// assert_eq!(motif.sites, Some(4));
+/// assert_eq!(motif.profile.value(b'T', 1), 1.);
+/// ```
+pub struct Reader<R> {
+    lines: Lines<R>,
+    /// `MOTIF` line which ended the previous matrix.
+    header: Option<String>,
+}
+
+impl<R: BufRead> Reader<R> {
+
+    pub fn new(reader: R) -> Reader<R> {
+        Reader { lines: Lines::new(reader), header: None }
+    }
+
+    /// Skips lines until the next `MOTIF` line.
+    fn read_header(&mut self) -> Result<Option<String>> {
+        while self.lines.read_line()? {
+            let line = self.lines.line.trim();
+            if let Some(alphabet) = line.strip_prefix("ALPHABET=") {
+                if alphabet.trim() != "ACGT" {
+                    return Err(self.lines.format_error("only ACGT alphabet is supported"));
+                }
+            }
+            if line.starts_with("MOTIF") {
+                return Ok(Some(line.to_owned()));
+            }
+        }
+        Ok(None)
+    }
+
+    fn read_motif(&mut self) -> Result<Option<Motif>> {
+        let header = match self.header.take() {
+            Some(header) => header,
+            None => match self.read_header()? {
+                Some(header) => header,
+                None => return Ok(None),
+            },
+        };
+        let mut fields = header.split_whitespace().skip(1);
+        let id = match fields.next() {
+            Some(id) => id.to_owned(),
+            None => return Err(self.lines.format_error("motif without identifier")),
+        };
+        let name = fields.next().map(|n| n.to_owned());
+
+        let mut width = None;
+        let mut sites = None;
+        loop {
+            self.lines.expect_line("letter-probability matrix")?;
+            let line = self.lines.line.trim();
+            if line.starts_with("MOTIF") {
+                return Err(self.lines.format_error("motif without letter-probability matrix"));
+            }
+            if let Some(params) = line.strip_prefix("letter-probability matrix:") {
+                let params: Vec<_> = params.split_whitespace().collect();
+                for pair in params.chunks(2) {
+                    let value = pair.get(1).and_then(|v| v.parse::<f64>().ok());
+                    match (pair[0], value) {
+                        ("alength=", Some(v)) if v != 4. =>
+                            return Err(self.lines.format_error("only ACGT alphabet is supported")),
+                        ("w=", Some(v)) => width = Some(v as usize),
+                        ("nsites=", Some(v)) => sites = Some(v.round() as usize),
+                        _ => (),
+                    }
+                }
+                break;
+            }
+        }
+
+        let mut rows = Vec::new();
+        while width.is_none_or(|w| rows.len() < w) {
+            if width.is_some() {
+                self.lines.expect_line("letter-probability matrix row")?;
+            } else if !self.lines.read_line()? {
+                break;
+            }
+            let line = self.lines.line.trim();
+            if line.is_empty() {
+                if width.is_none() && !rows.is_empty() {
+                    break;
+                }
+                continue;
+            }
+            let row: Vec<f64> = match line.split_whitespace().map(|v| v.parse()).collect() {
+                Ok(row) => row,
+                Err(_) if width.is_none() => {
+                    if line.starts_with("MOTIF") {
+                        self.header = Some(line.to_owned());
+                    }
+                    break;
+                },
+                Err(_) => return Err(self.lines.format_error("invalid letter-probability matrix row")),
+            };
+            if row.len() != 4 {
+                return Err(self.lines.format_error("expected 4 probabilities"));
+            }
+            rows.push([row[0], row[1], row[2], row[3]]);
+        }
+
+        let mut motif = Motif::new(&id, Profile::from_counts(&rows));
+        motif.name = name;
+        motif.sites = sites;
+        Ok(Some(motif))
+    }
+
+}
+
+impl Reader<Box<dyn BufRead>> {
+
+    /// Opens MEME file at `path`, possibly gzip compressed.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Reader<Box<dyn BufRead>>> {
+        let path = path.as_ref();
+        let mut reader = Reader::new(super::open(path)?);
+        reader.lines.path = Some(path.to_path_buf());
+        Ok(reader)
+    }
+
+}
+
+impl<R: BufRead> Iterator for Reader<R> {
+    type Item = Result<Motif>;
+
+    fn next(&mut self) -> Option<Result<Motif>> {
+        match self.read_motif() {
+            Ok(Some(motif)) => Some(Ok(motif)),
+            Ok(None) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+/// Writes motifs in MEME minimal motif format, starting with the version,
+/// alphabet and uniform background lines.
+///
+/// # Examples
+///
+/// ```
+/// use bio::data::meme::{Reader, Writer};
+/// use bio::data::motif::Motif;
+/// use bio::dna::Dna;
+///
+/// let motifs = vec![Motif::from_sites("m1", &[Dna::from_str("ACGT"), Dna::from_str("TCGT")])];
+/// let mut out = Vec::new();
+/// Writer::new(&mut out).write_all(&motifs).unwrap();
+///
+/// let read: Vec<_> = Reader::new(&out[..]).map(|m| m.unwrap()).collect();
+/// assert_eq!(read, motifs);
+/// ```
+pub struct Writer<W> {
+    writer: W,
+    header: bool,
+}
+
+impl<W: Write> Writer<W> {
+
+    pub fn new(writer: W) -> Writer<W> {
+        Writer { writer, header: false }
+    }
+
+    fn write_header(&mut self) -> io::Result<()> {
+        if !self.header {
+            self.header = true;
+            write!(self.writer, "MEME version 4\n\nALPHABET= ACGT\n\nstrands: + -\n\n")?;
+            write!(self.writer, "Background letter frequencies\nA 0.25 C 0.25 G 0.25 T 0.25\n\n")?;
+        }
+        Ok(())
+    }
+
+    pub fn write(&mut self, motif: &Motif) -> io::Result<()> {
+        self.write_header()?;
+        match motif.name {
+            Some(ref name) => writeln!(self.writer, "MOTIF {} {}", motif.id, name)?,
+            None => writeln!(self.writer, "MOTIF {}", motif.id)?,
+        }
+        let profile = &motif.profile;
+        write!(self.writer, "letter-probability matrix: alength= 4 w= {}", profile.len())?;
+        if let Some(sites) = motif.sites {
+            write!(self.writer, " nsites= {}", sites)?;
+        }
+        writeln!(self.writer)?;
+        for i in 0..profile.len() {
+            let row: Vec<_> = profile.column(i).iter()
+                .map(|&(_, p)| format!("{:.6}", p))
+                .collect();
+            writeln!(self.writer, " {}", row.join("  "))?;
+        }
+        writeln!(self.writer)
+    }
+
+    pub fn write_all(&mut self, motifs: &[Motif]) -> io::Result<()> {
+        for motif in motifs.iter() {
+            self.write(motif)?;
+        }
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{Reader, Writer};
+    use data::motif::Motif;
+    use dna::{Dna, Profile};
+
+    #[test]
+    fn test_roundtrip() {
+        let mut m1 = Motif::from_sites("m1", &[Dna::from_str("ACGT"), Dna::from_str("AGGT")]);
+        m1.name = Some("box".to_owned());
+        let m2 = Motif::new("m2", Profile::from_counts(&[[1., 1., 1., 1.], [0., 0., 0., 2.]]));
+        let motifs = vec![m1, m2];
+
+        let mut out = Vec::new();
+        Writer::new(&mut out).write_all(&motifs).unwrap();
+        let read: Vec<_> = Reader::new(&out[..]).map(|m| m.unwrap()).collect();
+        assert_eq!(read, motifs);
+    }
+
+    #[test]
+    fn test_without_width() {
+        let input = b"MOTIF m1\nletter-probability matrix:\n0.5 0.5 0 0\n1 0 0 0\nMOTIF m2\n\
+                      letter-probability matrix: alength= 4\n0 0 0 1\n";
+        let motifs: Vec<_> = Reader::new(&input[..]).map(|m| m.unwrap()).collect();
+        assert_eq!(motifs.len(), 2);
+        assert_eq!(motifs[0].profile.len(), 2);
+        assert_eq!(motifs[1].id, "m2");
+        assert_eq!(motifs[1].profile.len(), 1);
+    }
+
+    #[test]
+    fn test_invalid_row() {
+        let input = b"MOTIF m1\nletter-probability matrix: w= 2\n0.5 0.5 0 0\n1 0 0\n";
+        let err = Reader::new(&input[..]).next().unwrap().unwrap_err();
+        assert_eq!(err.line(), Some(4));
+    }
+
+}
@@ -0,0 +1,162 @@
+//!
+//! Memory-mapped genome access
+//!
+//! `MappedDna` maps a flat sequence file into memory instead of reading
+//! it, so random-access analyses over large references do not copy them.
+//!
+
+use std::fmt;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::ops;
+use std::path::Path;
+
+use memmap2::Mmap;
+
+use super::{Error, Result};
+use super::stream::Chunks;
+
+/// Read-only DNA sequence backed by a memory-mapped file.
+///
+/// The file should hold the sequence on a single line, optionally
+/// preceded by a FASTA header line. Wrapped FASTA files can be converted
+/// with `flatten`. Like `Dna`, it implements `Deref` to `&[u8]`.
+///
+/// # Examples
+///
+/// ```
+/// use std::env;
+/// use std::fs::{self, File};
+/// use std::io::Write;
+/// use std::process;
+/// use bio::data::mmap::MappedDna;
+/// use bio::seq;
+///
+/// let path = env::temp_dir().join(format!("bio_mapped_dna_doc_{}.txt", process::id()));
+/// File::create(&path).unwrap().write_all(b">ref\nACGTTACGT\n").unwrap();
+///
+/// let genome = MappedDna::open(&path).unwrap();
+/// assert_eq!(genome.len(), 9);
+/// assert_eq!(seq::find(&genome, b"ACG"), [0, 5]);
+/// # drop(genome);
+/// fs::remove_file(&path).unwrap();
+/// ```
+pub struct MappedDna {
+    mmap: Option<Mmap>,
+    start: usize,
+    end: usize,
+}
+
+impl MappedDna {
+
+    /// Maps sequence file at `path`.
+    ///
+    /// The mapping is only valid while the file is not modified, the file
+    /// should not be changed by other processes while it is mapped.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<MappedDna> {
+        let path = path.as_ref();
+        let file = File::open(path).map_err(|e| Error::from(e).with_path(path))?;
+        let len = file.metadata().map_err(|e| Error::from(e).with_path(path))?.len();
+        if len == 0 {
+            // empty files can not be mapped
+            return Ok(MappedDna { mmap: None, start: 0, end: 0 });
+        }
+        let mmap = unsafe { Mmap::map(&file) }.map_err(|e| Error::from(e).with_path(path))?;
+
+        let (start, line) = if mmap.starts_with(b">") {
+            match mmap.iter().position(|&b| b == b'\n') {
+                Some(i) => (i + 1, 2),
+                None => (mmap.len(), 1),
+            }
+        } else {
+            (0, 1)
+        };
+        let end = mmap.iter()
+            .rposition(|b| !b.is_ascii_whitespace())
+            .map_or(start, |i| (i + 1).max(start));
+        if let Some(i) = mmap[start..end].iter().position(|&b| b == b'\n') {
+            let next_line = line + 1 + mmap[start..start + i].iter().filter(|&&b| b == b'\n').count();
+            let message = "sequence spans several lines, flatten the file first";
+            return Err(Error::format(next_line, message).with_path(path));
+        }
+
+        Ok(MappedDna { mmap: Some(mmap), start, end })
+    }
+
+}
+
+impl ops::Deref for MappedDna {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self.mmap {
+            Some(ref mmap) => &mmap[self.start..self.end],
+            None => &[],
+        }
+    }
+}
+
+impl AsRef<[u8]> for MappedDna {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl fmt::Debug for MappedDna {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MappedDna {{ len: {} }}", self.len())
+    }
+}
+
+/// Writes the first record of a plain text or FASTA file `src` to `dst`
+/// as a single sequence line, suitable for `MappedDna::open`. Input is
+/// streamed, so it is never fully loaded into memory.
+pub fn flatten<P: AsRef<Path>, Q: AsRef<Path>>(src: P, dst: Q) -> Result<()> {
+    let dst = dst.as_ref();
+    let file = File::create(dst).map_err(|e| Error::from(e).with_path(dst))?;
+    let mut out = BufWriter::new(file);
+    let write = |out: &mut BufWriter<File>, bytes: &[u8]| out.write_all(bytes)
+        .map_err(|e| Error::from(e).with_path(dst));
+
+    for chunk in Chunks::from_file(&src, 1 << 16, 0)? {
+        let chunk = chunk?;
+        if chunk.record > 0 {
+            break;
+        }
+        write(&mut out, chunk.fresh())?;
+    }
+    write(&mut out, b"\n")?;
+    out.flush().map_err(|e| Error::from(e).with_path(dst))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::fs::File;
+    use std::io::Write;
+
+    use data::tests::TempPath;
+    use super::{MappedDna, flatten};
+
+    #[test]
+    fn test_open_plain() {
+        let path = TempPath::new("mapped_dna_plain.txt");
+        File::create(&path).unwrap().write_all(b"GATTACA\r\n").unwrap();
+        let dna = MappedDna::open(&path).unwrap();
+        assert_eq!(&dna[..], b"GATTACA");
+    }
+
+    #[test]
+    fn test_open_wrapped() {
+        let path = TempPath::new("mapped_dna_wrapped.fa");
+        File::create(&path).unwrap().write_all(b">r1\nACGT\nACGT\n>r2\nTT\n").unwrap();
+        let err = MappedDna::open(&path).unwrap_err();
+        assert_eq!(err.line(), Some(3));
+
+        let flat = TempPath::new("mapped_dna_flat.txt");
+        flatten(&path, &flat).unwrap();
+        let dna = MappedDna::open(&flat).unwrap();
+        assert_eq!(&dna[..], b"ACGTACGT");
+    }
+
+}
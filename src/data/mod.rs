@@ -0,0 +1,327 @@
+//!
+//! Utilities to work with datasets
+//!
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::str;
+
+use flate2::bufread::MultiGzDecoder;
+
+mod error;
+pub mod bed;
+pub mod fasta;
+pub mod fastq;
+pub mod genbank;
+pub mod gff;
+pub mod interval;
+pub mod jaspar;
+pub mod meme;
+pub mod mmap;
+pub mod motif;
+pub mod stream;
+pub mod transfac;
+pub mod twobit;
+
+pub use self::error::{Error, Result};
+
+pub struct Dataset {
+    pub bytes: Vec<u8>,
+}
+
+impl Dataset {
+
+    /// Reads the whole file at `path`, checking that it is valid UTF-8.
+    /// Gzip compressed files are decompressed.
+    pub fn read_text<P: AsRef<Path>>(path: P) -> Result<Dataset> {
+        let path = path.as_ref();
+        let mut contents = Vec::new();
+        open(path)?
+            .read_to_end(&mut contents)
+            .map_err(|e| Error::from(e).with_path(path))?;
+        if let Err(e) = str::from_utf8(&contents) {
+            return Err(Error::encoding(None, &e).with_path(path));
+        }
+        Ok(Dataset { bytes: contents })
+    }
+
+    /// Reads the sequence of the first record of FASTA file at `path`.
+    pub fn read_fasta<P: AsRef<Path>>(path: P) -> Result<Dataset> {
+        let path = path.as_ref();
+        let raw = Dataset::read_text(path)?;
+        let bytes = match fasta::Reader::new(raw.bytes.as_slice()).preserve_case().next() {
+            Some(record) => record.map_err(|e| e.with_path(path))?.seq.to_string().into_bytes(),
+            None => Vec::new(),
+        };
+
+        Ok(Dataset { bytes })
+    }
+
+    /// Reads the whole file at `path`.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the file can not be read. See `read_text` for the
+    /// fallible version.
+    pub fn open_text<P: AsRef<Path>>(path: P) -> Dataset {
+        Dataset::read_text(path).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Opens FASTA file and reads the sequence of the first record.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the file can not be read. See `read_fasta` for the
+    /// fallible version.
+    pub fn open_fasta<P: AsRef<Path>>(path: P) -> Dataset {
+        Dataset::read_fasta(path).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Dataset contents as a string slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `bytes` are not valid UTF-8, which is only possible
+    /// when they were modified after reading.
+    pub fn contents(&self) -> &str {
+        str::from_utf8(&self.bytes).expect("dataset is not valid utf-8")
+    }
+
+    pub fn parse<T: str::FromStr>(&self) -> ::std::result::Result<T, T::Err> {
+        self.contents().trim().parse::<T>()
+    }
+
+    pub fn lines(&self) -> Vec<&str> {
+        self.contents().lines().collect()
+    }
+
+}
+
+/// Opens file at `path` for buffered reading. Gzip and BGZF compressed
+/// files are detected by their magic bytes and decompressed on the fly.
+pub fn open<P: AsRef<Path>>(path: P) -> Result<Box<dyn BufRead>> {
+    let path = path.as_ref();
+    let file = File::open(path).map_err(|e| Error::from(e).with_path(path))?;
+    decompress(BufReader::new(file)).map_err(|e| Error::from(e).with_path(path))
+}
+
+/// Wraps `reader` into a gzip decoder if the input starts with gzip magic
+/// bytes, returns plain `reader` otherwise. Concatenated gzip members,
+/// such as BGZF blocks, are decoded as a single stream.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Read;
+/// use bio::data::decompress;
+///
+/// let mut plain = String::new();
+/// decompress(&b">r1\nACGT\n"[..]).unwrap().read_to_string(&mut plain).unwrap();
+/// assert_eq!(plain, ">r1\nACGT\n");
+/// ```
+pub fn decompress<'a, R: BufRead + 'a>(mut reader: R) -> io::Result<Box<dyn BufRead + 'a>> {
+    let compressed = reader.fill_buf()?.starts_with(&GZIP_MAGIC);
+    if compressed {
+        Ok(Box::new(BufReader::new(MultiGzDecoder::new(reader))))
+    } else {
+        Ok(Box::new(reader))
+    }
+}
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Line reader of the text formats, keeps line number and file path for
+/// error reporting.
+struct Lines<R> {
+    reader: R,
+    buf: Vec<u8>,
+    line: String,
+    line_no: usize,
+    path: Option<PathBuf>,
+}
+
+impl<R: BufRead> Lines<R> {
+
+    fn new(reader: R) -> Lines<R> {
+        Lines {
+            reader,
+            buf: Vec::new(),
+            line: String::new(),
+            line_no: 0,
+            path: None,
+        }
+    }
+
+    /// Reads next line into `line`, drops trailing newline. Returns
+    /// `false` at the end of input.
+    fn read_line(&mut self) -> Result<bool> {
+        self.buf.clear();
+        let read = self.reader.read_until(b'\n', &mut self.buf)
+            .map_err(|e| self.error(Error::from(e)))?;
+        self.line.clear();
+        if read == 0 {
+            return Ok(false);
+        }
+        self.line_no += 1;
+        match str::from_utf8(&self.buf) {
+            Ok(line) => self.line.push_str(line.trim_end_matches(&['\n', '\r'][..])),
+            Err(e) => return Err(self.error(Error::encoding(Some(self.line_no), &e))),
+        }
+        Ok(true)
+    }
+
+    /// Reads next line of a started record, end of input is an error.
+    fn expect_line(&mut self, what: &str) -> Result<()> {
+        if self.read_line()? {
+            Ok(())
+        } else {
+            let message = format!("unexpected end of input, expected {}", what);
+            Err(self.error(Error::format(self.line_no + 1, &message)))
+        }
+    }
+
+    /// Format error at the current line.
+    fn format_error(&self, message: &str) -> Error {
+        self.error(Error::format(self.line_no, message))
+    }
+
+    /// Attaches file path to `err`.
+    fn error(&self, err: Error) -> Error {
+        match self.path {
+            Some(ref path) => err.with_path(path),
+            None => err,
+        }
+    }
+
+}
+
+/// Splits record header into identifier and optional description.
+fn split_header(header: &str) -> (&str, Option<&str>) {
+    let header = header.trim();
+    match header.find(char::is_whitespace) {
+        Some(i) => (&header[..i], Some(header[i..].trim_start())),
+        None => (header, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::env;
+    use std::fs::{self, File};
+    use std::io::Write;
+    use std::ops::Deref;
+    use std::path::{Path, PathBuf};
+    use std::process;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+    use test::Bencher;
+
+    use super::{Dataset, Error, fasta};
+
+    static SALMONELLA_ENTERICA: &'static str = "data/Salmonella_enterica.txt";
+
+    /// Path in the temporary directory unique to this process and call,
+    /// so concurrent test runs do not share files. The file is removed on
+    /// drop.
+    pub struct TempPath(PathBuf);
+
+    impl TempPath {
+
+        pub fn new(name: &str) -> TempPath {
+            static COUNTER: AtomicUsize = AtomicUsize::new(0);
+            let n = COUNTER.fetch_add(1, Ordering::SeqCst);
+            TempPath(env::temp_dir().join(format!("bio_{}_{}_{}", process::id(), n, name)))
+        }
+
+    }
+
+    impl Deref for TempPath {
+        type Target = PathBuf;
+
+        fn deref(&self) -> &PathBuf {
+            &self.0
+        }
+    }
+
+    impl AsRef<Path> for TempPath {
+        fn as_ref(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    impl Drop for TempPath {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.0);
+        }
+    }
+
+    #[test]
+    fn test_read_missing_file() {
+        let path = "data/no_such_dataset.txt";
+        match Dataset::read_text(path) {
+            Err(ref e @ Error::Io { .. }) => assert_eq!(e.path(), Some(Path::new(path))),
+            _ => panic!("expected io error"),
+        }
+    }
+
+    #[test]
+    fn test_read_invalid_utf8() {
+        let path = TempPath::new("read_invalid_utf8.txt");
+        File::create(&path).unwrap().write_all(b"ACGT\xfe").unwrap();
+        match Dataset::read_text(&path) {
+            Err(Error::Encoding { valid_up_to, .. }) => assert_eq!(valid_up_to, 4),
+            _ => panic!("expected encoding error"),
+        }
+    }
+
+    #[test]
+    fn test_read_gzip() {
+        let path = TempPath::new("read_gzip.fa.gz");
+        {
+            // two gzip members, as written by BGZF
+            let mut file = File::create(&path).unwrap();
+            for part in [&b">r1 first\nACGT\n"[..], &b"TTGA\n>r2\nCCCC\n"[..]].iter() {
+                let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(part).unwrap();
+                file.write_all(&encoder.finish().unwrap()).unwrap();
+            }
+        }
+        let dataset = Dataset::read_text(&path).unwrap();
+        assert_eq!(dataset.contents(), ">r1 first\nACGT\nTTGA\n>r2\nCCCC\n");
+
+        let records: Vec<_> = fasta::Reader::from_file(&path).unwrap()
+            .map(|r| r.unwrap().seq.to_string())
+            .collect();
+        assert_eq!(records, ["ACGTTTGA", "CCCC"]);
+    }
+
+    #[bench]
+    fn bench_open_text(b: &mut Bencher) {
+        b.iter(|| super::Dataset::open_text(SALMONELLA_ENTERICA));
+    }
+
+    #[bench]
+    fn bench_open_fasta(b: &mut Bencher) {
+        b.iter(|| super::Dataset::open_fasta(SALMONELLA_ENTERICA));
+    }
+
+    #[bench]
+    fn bench_open_mmap(b: &mut Bencher) {
+        // generated genome of 4 Mb, mapping does not depend on contents
+        let flat = TempPath::new("open_mmap.txt");
+        {
+            let mut file = File::create(&flat).unwrap();
+            file.write_all(b">generated\n").unwrap();
+            for _ in 0..1 << 16 {
+                file.write_all(b"ACGTTGCAGGATCCATGCATTTAAACCCGGGTTACGTAGCTAGCTAACGGCCTTAAGGCATGCA").unwrap();
+            }
+            file.write_all(b"\n").unwrap();
+        }
+        b.iter(|| super::mmap::MappedDna::open(&flat).unwrap());
+    }
+
+}
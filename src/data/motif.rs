@@ -0,0 +1,85 @@
+//!
+//! Sequence motifs shared by the motif database formats
+//!
+
+use ::dna::{Dna, Profile};
+
+/// Number of sites used to write count matrices of motifs which do not
+/// know the number of sites they were built from.
+pub const DEFAULT_SITES: usize = 100;
+
+/// Named position frequency matrix, as stored in motif databases.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Motif {
+    /// Motif identifier, such as JASPAR matrix ID `MA0001.1`.
+    pub id: String,
+    /// Alternative name, usually the name of the binding factor.
+    pub name: Option<String>,
+    pub profile: Profile,
+    /// Number of sites the profile was built from.
+    pub sites: Option<usize>,
+}
+
+impl Motif {
+
+    pub fn new(id: &str, profile: Profile) -> Motif {
+        Motif {
+            id: id.to_owned(),
+            name: None,
+            profile,
+            sites: None,
+        }
+    }
+
+    /// Motif of aligned sites, such as returned by
+    /// `dna::greedy_motif_search` or `dna::gibbs_sampler`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bio::data::motif::Motif;
+    /// use bio::dna::Dna;
+    ///
+    /// let sites = [Dna::from_str("ACGT"), Dna::from_str("ACGA")];
+    /// let motif = Motif::from_sites("m1", &sites);
+    ///
+    /// assert_eq!(motif.sites, Some(2));
+    /// assert_eq!(motif.profile.value(b'T', 3), 0.5);
+    /// ```
+    pub fn from_sites(id: &str, sites: &[Dna]) -> Motif {
+        Motif {
+            sites: Some(sites.len()),
+            ..Motif::new(id, Profile::from_motifs(sites))
+        }
+    }
+
+    /// Builds motif from count matrix columns in `A`, `C`, `G`, `T` order.
+    /// The number of sites is the largest column sum.
+    pub fn from_counts(id: &str, counts: &[[f64; 4]]) -> Motif {
+        let sites = counts.iter()
+            .map(|col| col.iter().sum::<f64>())
+            .fold(0., f64::max);
+        Motif {
+            sites: if counts.is_empty() { None } else { Some(sites.round() as usize) },
+            ..Motif::new(id, Profile::from_counts(counts))
+        }
+    }
+
+    /// Count matrix columns in `A`, `C`, `G`, `T` order, computed from the
+    /// profile and the number of sites, `DEFAULT_SITES` if unknown.
+    pub fn counts(&self) -> Vec<[f64; 4]> {
+        let sites = self.sites.unwrap_or(DEFAULT_SITES) as f64;
+        (0..self.profile.len())
+            .map(|i| {
+                let col = self.profile.column(i);
+                let sum: f64 = col.iter().map(|&(_, p)| p).sum();
+                let mut counts = [0.; 4];
+                for (n, &(_, p)) in counts.iter_mut().zip(col.iter()) {
+                    *n = if sum > 0. { (p / sum * sites).round() } else { 0. };
+                }
+                counts
+            })
+            .collect()
+    }
+
+}
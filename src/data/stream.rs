@@ -0,0 +1,358 @@
+//!
+//! Bounded-memory reading of long sequences
+//!
+//! `Chunks` splits sequences of a plain text or FASTA input into
+//! overlapping pieces of fixed size, so a whole genome never has to be
+//! held in memory.
+//!
+
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+
+use ::dna::{iupac, AsciiError, Dna};
+use super::{Error, Result};
+
+/// A piece of sequence read by `Chunks`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chunk {
+    /// Index of the record the chunk belongs to, starting from 0.
+    pub record: usize,
+    /// Position of the first base of the chunk within its record.
+    pub offset: usize,
+    /// Number of leading bases shared with the previous chunk.
+    pub overlap: usize,
+    pub seq: Dna,
+}
+
+impl Chunk {
+
+    /// Bases which were not contained in the previous chunk.
+    pub fn fresh(&self) -> &[u8] {
+        &self.seq[self.overlap..]
+    }
+
+    /// Returns windows of size `k` together with their positions within
+    /// the record, skipping windows that were fully contained in the
+    /// previous chunk. Every window of a record is yielded exactly once
+    /// when chunks overlap by at least `k - 1` bases. There are no windows
+    /// of size 0.
+    pub fn windows(&self, k: usize) -> Box<dyn Iterator<Item = (usize, &[u8])> + '_> {
+        if k == 0 {
+            return Box::new(::std::iter::empty());
+        }
+        let skip = (self.overlap + 1).saturating_sub(k);
+        let offset = self.offset;
+        let it = self.seq.windows(k)
+            .enumerate()
+            .skip(skip)
+            .map(move |(i, w)| (offset + i, w));
+        Box::new(it)
+    }
+
+}
+
+/// Iterator over sequence chunks of a plain text or FASTA input.
+///
+/// Header lines start a new record, whitespace and line breaks are
+/// dropped. Chunks never span two records. Bases must be IUPAC nucleotide
+/// codes, such as `N`, other bytes are reported as format errors.
+/// Lowercase (soft-masked) bases are converted to upper case unless the
+/// iterator was switched to `preserve_case`, as in `fasta::Reader`.
+/// Consecutive chunks of the same record share `overlap` bases, so k-mer
+/// analyses with `k <= overlap + 1` see every window.
+///
+/// # Examples
+///
+/// ```
+/// use bio::data::stream::Chunks;
+///
+/// let input = b">chr1\nACGTA\nCGT\n>chr2\nTT\n";
+/// let chunks: Vec<_> = Chunks::new(&input[..], 4, 1)
+///     .map(|c| c.unwrap())
+///     .map(|c| (c.record, c.offset, c.seq.to_string()))
+///     .collect();
+///
+/// assert_eq!(chunks, [
+///     (0, 0, "ACGT".to_owned()),
+///     (0, 3, "TACG".to_owned()),
+///     (0, 6, "GT".to_owned()),
+///     (1, 0, "TT".to_owned()),
+/// ]);
+/// ```
+///
+/// Reading a genome from stdin:
+///
+/// ```no_run
+/// use std::io;
+/// use bio::data::stream::Chunks;
+///
+/// let stdin = io::stdin();
+/// for chunk in Chunks::new(stdin.lock(), 1 << 20, 8) {
+///     let chunk = chunk.unwrap();
+///     println!("{} {}", chunk.offset, chunk.fresh().len());
+/// }
+/// ```
+pub struct Chunks<R> {
+    reader: R,
+    path: Option<PathBuf>,
+    splitter: Splitter,
+}
+
+impl<R: BufRead> Chunks<R> {
+
+    /// Creates chunk iterator with the given chunk `size` and the number
+    /// of bases shared by consecutive chunks.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `overlap` is not less than `size`.
+    pub fn new(reader: R, size: usize, overlap: usize) -> Chunks<R> {
+        assert!(overlap < size, "chunk overlap should be less than its size");
+        Chunks {
+            reader,
+            path: None,
+            splitter: Splitter {
+                size,
+                overlap,
+                buf: Vec::with_capacity(size),
+                record: 0,
+                offset: 0,
+                carried: 0,
+                in_header: false,
+                line_start: true,
+                started: false,
+                uppercase: true,
+                line: 1,
+                column: 0,
+            },
+        }
+    }
+
+    /// Keep lowercase (soft-masked) bases as is.
+    pub fn preserve_case(mut self) -> Chunks<R> {
+        self.splitter.uppercase = false;
+        self
+    }
+
+    fn read_chunk(&mut self) -> Result<Option<Chunk>> {
+        loop {
+            let (consumed, chunk) = {
+                let bytes = self.reader.fill_buf()?;
+                if bytes.is_empty() {
+                    return Ok(self.splitter.finish_record());
+                }
+                self.splitter.feed(bytes)
+            };
+            self.reader.consume(consumed);
+            let chunk = chunk?;
+            if chunk.is_some() {
+                return Ok(chunk);
+            }
+        }
+    }
+
+}
+
+impl Chunks<Box<dyn BufRead>> {
+
+    /// Reads chunks of a file at `path`, possibly gzip compressed.
+    pub fn from_file<P: AsRef<Path>>(path: P, size: usize, overlap: usize) -> Result<Chunks<Box<dyn BufRead>>> {
+        let path = path.as_ref();
+        let mut chunks = Chunks::new(super::open(path)?, size, overlap);
+        chunks.path = Some(path.to_path_buf());
+        Ok(chunks)
+    }
+
+}
+
+/// Splitting state of `Chunks`, kept apart from the reader to borrow the
+/// reader buffer while feeding it.
+struct Splitter {
+    size: usize,
+    overlap: usize,
+    buf: Vec<u8>,
+    record: usize,
+    offset: usize,
+    carried: usize,
+    in_header: bool,
+    line_start: bool,
+    started: bool,
+    uppercase: bool,
+    /// Line number and byte index within the line of the next byte.
+    line: usize,
+    column: usize,
+}
+
+impl Splitter {
+
+    /// Takes accumulated bases as a chunk, keeping the overlap for the
+    /// next one.
+    fn take_chunk(&mut self) -> Chunk {
+        let chunk = Chunk {
+            record: self.record,
+            offset: self.offset,
+            overlap: self.carried,
+            seq: Dna::from_slice(&self.buf),
+        };
+        let keep = self.overlap.min(self.buf.len());
+        let drop = self.buf.len() - keep;
+        self.buf.drain(..drop);
+        self.offset += drop;
+        self.carried = keep;
+        chunk
+    }
+
+    /// Finishes current record, returns its last chunk if it has fresh bases.
+    fn finish_record(&mut self) -> Option<Chunk> {
+        let chunk = if self.buf.len() > self.carried {
+            Some(self.take_chunk())
+        } else {
+            None
+        };
+        self.buf.clear();
+        self.offset = 0;
+        self.carried = 0;
+        chunk
+    }
+
+    /// Consumes `bytes` until a chunk is complete or an invalid base is
+    /// found. Returns the number of consumed bytes and the chunk, if any.
+    fn feed(&mut self, bytes: &[u8]) -> (usize, Result<Option<Chunk>>) {
+        for (i, &b) in bytes.iter().enumerate() {
+            let column = self.column;
+            if b == b'\n' {
+                self.line += 1;
+                self.column = 0;
+            } else {
+                self.column += 1;
+            }
+            if self.in_header {
+                if b == b'\n' {
+                    self.in_header = false;
+                    self.line_start = true;
+                }
+                continue;
+            }
+            if self.line_start && b == b'>' {
+                self.in_header = true;
+                self.line_start = false;
+                let chunk = self.finish_record();
+                if self.started {
+                    self.record += 1;
+                }
+                self.started = true;
+                if chunk.is_some() {
+                    return (i + 1, Ok(chunk));
+                }
+                continue;
+            }
+            self.line_start = b == b'\n';
+            if b.is_ascii_whitespace() {
+                continue;
+            }
+            let upper = b.to_ascii_uppercase();
+            if !iupac::is_valid(upper) {
+                let message = AsciiError::new(column, b).to_string();
+                return (i + 1, Err(Error::format(self.line, &message)));
+            }
+            self.started = true;
+            self.buf.push(if self.uppercase { upper } else { b });
+            if self.buf.len() == self.size {
+                return (i + 1, Ok(Some(self.take_chunk())));
+            }
+        }
+        (bytes.len(), Ok(None))
+    }
+
+}
+
+impl<R: BufRead> Iterator for Chunks<R> {
+    type Item = Result<Chunk>;
+
+    fn next(&mut self) -> Option<Result<Chunk>> {
+        match self.read_chunk() {
+            Ok(Some(chunk)) => Some(Ok(chunk)),
+            Ok(None) => None,
+            Err(e) => Some(Err(match self.path {
+                Some(ref path) => e.with_path(path),
+                None => e,
+            })),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::fs::File;
+    use std::io::Write;
+
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+
+    use data::tests::TempPath;
+    use super::Chunks;
+
+    #[test]
+    fn test_plain_sequence() {
+        let input = b"ACGTAC\nGTACGT\n";
+        let chunks: Vec<_> = Chunks::new(&input[..], 5, 2)
+            .map(|c| c.unwrap())
+            .collect();
+        let seqs: Vec<_> = chunks.iter().map(|c| c.seq.as_str()).collect();
+        let fresh: Vec<u8> = chunks.iter().flat_map(|c| c.fresh().to_vec()).collect();
+        assert_eq!(seqs, ["ACGTA", "TACGT", "GTACG", "CGT"]);
+        assert_eq!(fresh, b"ACGTACGTACGT");
+        assert!(chunks.iter().all(|c| c.record == 0));
+        assert_eq!(chunks[0].windows(0).count(), 0);
+    }
+
+    #[test]
+    fn test_case_and_validation() {
+        let input = b">r\nACgtN\nac\n";
+        let seqs: Vec<_> = Chunks::new(&input[..], 4, 0)
+            .map(|c| c.unwrap().seq.to_string())
+            .collect();
+        assert_eq!(seqs, ["ACGT", "NAC"]);
+        let chunk = Chunks::new(&input[..], 8, 0).preserve_case().next().unwrap().unwrap();
+        assert_eq!(chunk.seq.as_str(), "ACgtNac");
+
+        let input = ">r\nACGT\nAC\u{e9}G\n".as_bytes();
+        let err = Chunks::new(input, 4, 0).find(|c| c.is_err()).unwrap().unwrap_err();
+        assert_eq!(err.line(), Some(3));
+        assert_eq!(err.to_string(), "line 3: invalid ascii: invalid byte 0xc3 at index 2");
+    }
+
+    #[test]
+    fn test_from_file_gzip() {
+        let path = TempPath::new("chunks_from_file.fa.gz");
+        {
+            let mut encoder = GzEncoder::new(File::create(&path).unwrap(), Compression::default());
+            encoder.write_all(b">r1\nACGTAC\n>r2\nGGX\n").unwrap();
+            encoder.finish().unwrap();
+        }
+        let mut chunks = Chunks::from_file(&path, 4, 1).unwrap();
+        let seqs: Vec<_> = chunks.by_ref()
+            .take(2)
+            .map(|c| c.unwrap().seq.to_string())
+            .collect();
+        assert_eq!(seqs, ["ACGT", "TAC"]);
+        let err = chunks.next().unwrap().unwrap_err();
+        assert_eq!((err.path(), err.line()), (Some(path.as_path()), Some(4)));
+    }
+
+    #[test]
+    fn test_windows_seen_once() {
+        let input = b">r\nAACCGGTTAACCGGTT\n";
+        let expected: Vec<_> = input[3..19].windows(3).enumerate().collect();
+        let windows: Vec<_> = Chunks::new(&input[..], 4, 2)
+            .map(|c| c.unwrap())
+            .flat_map(|c| c.windows(3).map(|(i, w)| (i, w.to_vec())).collect::<Vec<_>>())
+            .collect();
+        assert_eq!(windows.len(), expected.len());
+        for (&(i, w), &(j, ref v)) in expected.iter().zip(windows.iter()) {
+            assert_eq!((i, w), (j, &v[..]));
+        }
+    }
+
+}
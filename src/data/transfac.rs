@@ -0,0 +1,221 @@
+//!
+//! TRANSFAC matrix format reader and writer
+//!
+//! Records are identified by the `AC` accession, the `ID` line is used as
+//! the motif name. Lines other than the matrix, `AC`, `ID` and the `//`
+//! record terminator are skipped.
+//!
+
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+use super::{Lines, Result, split_header};
+use super::motif::Motif;
+
+/// Iterator over matrices of a TRANSFAC file.
+///
+/// # Examples
+///
+/// ```
+/// use bio::data::transfac::Reader;
+///
+/// let input = b"AC  M00001\nXX\nID  V$MYOD_01\nXX\n\
+///               P0      A      C      G      T\n\
+///               01      1      2      2      0      S\n\
+///               02      2      1      2      0      R\nXX\n//\n";
+/// let motif = Reader::new(&input[..]).next().unwrap().unwrap();
+///
+/// assert_eq!(motif.id, "M00001");
+/// assert_eq!(motif.name, Some("V$MYOD_01".to_owned()));
+/// assert_eq!(motif.counts(), [[1., 2., 2., 0.], [2., 1., 2., 0.]]);
+/// ```
+pub struct Reader<R> {
+    lines: Lines<R>,
+}
+
+impl<R: BufRead> Reader<R> {
+
+    pub fn new(reader: R) -> Reader<R> {
+        Reader { lines: Lines::new(reader) }
+    }
+
+    fn read_motif(&mut self) -> Result<Option<Motif>> {
+        let mut accession = None;
+        let mut identifier = None;
+        // column of each nucleotide in the matrix rows
+        let mut order: Option<Vec<usize>> = None;
+        let mut counts = Vec::new();
+        let mut started = false;
+
+        while self.lines.read_line()? {
+            let line = self.lines.line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            if line.starts_with("//") {
+                if started {
+                    break;
+                }
+                continue;
+            }
+            started = true;
+            let (code, value) = split_header(line);
+            let value = value.unwrap_or("");
+            match code {
+                "AC" => accession = Some(value.to_owned()),
+                "ID" => identifier = Some(value.to_owned()),
+                "P0" | "PO" => {
+                    let letters: Vec<_> = value.split_whitespace().collect();
+                    let columns: Option<Vec<_>> = ["A", "C", "G", "T"].iter()
+                        .map(|n| letters.iter().position(|l| l.eq_ignore_ascii_case(n)))
+                        .collect();
+                    match columns {
+                        Some(columns) if letters.len() == 4 => order = Some(columns),
+                        _ => return Err(self.lines.format_error("expected A, C, G and T matrix columns")),
+                    }
+                },
+                _ if order.is_some() && code.bytes().all(|b| b.is_ascii_digit()) => {
+                    let order = order.as_ref().unwrap();
+                    let values: Vec<_> = value.split_whitespace().take(4)
+                        .map(|n| n.parse::<f64>())
+                        .collect::<::std::result::Result<_, _>>()
+                        .map_err(|_| self.lines.format_error("invalid count"))?;
+                    if values.len() != 4 {
+                        return Err(self.lines.format_error("expected 4 counts"));
+                    }
+                    counts.push([values[order[0]], values[order[1]], values[order[2]], values[order[3]]]);
+                },
+                _ => (),
+            }
+        }
+
+        if !started {
+            return Ok(None);
+        }
+        if order.is_none() {
+            return Err(self.lines.format_error("record without matrix"));
+        }
+        let (id, name) = match (accession, identifier) {
+            (Some(ac), id) => (ac, id),
+            (None, Some(id)) => (id, None),
+            (None, None) => return Err(self.lines.format_error("record without AC or ID")),
+        };
+        let mut motif = Motif::from_counts(&id, &counts);
+        motif.name = name;
+        Ok(Some(motif))
+    }
+
+}
+
+impl Reader<Box<dyn BufRead>> {
+
+    /// Opens TRANSFAC file at `path`, possibly gzip compressed.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Reader<Box<dyn BufRead>>> {
+        let path = path.as_ref();
+        let mut reader = Reader::new(super::open(path)?);
+        reader.lines.path = Some(path.to_path_buf());
+        Ok(reader)
+    }
+
+}
+
+impl<R: BufRead> Iterator for Reader<R> {
+    type Item = Result<Motif>;
+
+    fn next(&mut self) -> Option<Result<Motif>> {
+        match self.read_motif() {
+            Ok(Some(motif)) => Some(Ok(motif)),
+            Ok(None) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+/// Writes motifs as TRANSFAC count matrices, see `Motif::counts`.
+///
+/// # Examples
+///
+/// ```
+/// use bio::data::motif::Motif;
+/// use bio::data::transfac::Writer;
+/// use bio::dna::Dna;
+///
+/// let motif = Motif::from_sites("m1", &[Dna::from_str("AC"), Dna::from_str("AG")]);
+/// let mut out = Vec::new();
+/// Writer::new(&mut out).write(&motif).unwrap();
+///
+/// assert_eq!(String::from_utf8(out).unwrap(),
+///            "AC  m1\nXX\nP0      A      C      G      T\n\
+///             01      2      0      0      0\n02      0      1      1      0\nXX\n//\n");
+/// ```
+pub struct Writer<W> {
+    writer: W,
+}
+
+impl<W: Write> Writer<W> {
+
+    pub fn new(writer: W) -> Writer<W> {
+        Writer { writer }
+    }
+
+    pub fn write(&mut self, motif: &Motif) -> io::Result<()> {
+        writeln!(self.writer, "AC  {}\nXX", motif.id)?;
+        if let Some(ref name) = motif.name {
+            writeln!(self.writer, "ID  {}\nXX", name)?;
+        }
+        writeln!(self.writer, "P0      A      C      G      T")?;
+        for (i, col) in motif.counts().iter().enumerate() {
+            writeln!(self.writer, "{:02} {:>6} {:>6} {:>6} {:>6}", i + 1, col[0], col[1], col[2], col[3])?;
+        }
+        writeln!(self.writer, "XX\n//")
+    }
+
+    pub fn write_all(&mut self, motifs: &[Motif]) -> io::Result<()> {
+        for motif in motifs.iter() {
+            self.write(motif)?;
+        }
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{Reader, Writer};
+    use data::motif::Motif;
+
+    #[test]
+    fn test_roundtrip() {
+        let mut m1 = Motif::from_counts("MA0001.1", &[[0., 94., 1., 2.], [3., 75., 0., 19.]]);
+        m1.name = Some("AGL3".to_owned());
+        let m2 = Motif::from_counts("M00002", &[[5., 5., 0., 0.]]);
+        let motifs = vec![m1, m2];
+
+        let mut out = Vec::new();
+        Writer::new(&mut out).write_all(&motifs).unwrap();
+        let read: Vec<_> = Reader::new(&out[..]).map(|m| m.unwrap()).collect();
+        assert_eq!(read, motifs);
+    }
+
+    #[test]
+    fn test_column_order() {
+        let input = b"ID  m1\nPO  T G C A\n1 4 0 0 1\n2 0 5 0 0\n//\n";
+        let motif = Reader::new(&input[..]).next().unwrap().unwrap();
+        assert_eq!(motif.id, "m1");
+        assert_eq!(motif.name, None);
+        assert_eq!(motif.counts(), [[1., 0., 0., 4.], [0., 0., 5., 0.]]);
+    }
+
+    #[test]
+    fn test_invalid_count() {
+        let input = b"AC  m1\nP0 A C G T\n01 1 x 0 0\n//\n";
+        let err = Reader::new(&input[..]).next().unwrap().unwrap_err();
+        assert_eq!(err.line(), Some(3));
+    }
+
+}
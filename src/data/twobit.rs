@@ -0,0 +1,369 @@
+//!
+//! UCSC 2bit format reader and writer
+//!
+//! The 2bit format stores 4 bases per byte, with separate lists of
+//! N-blocks and soft-masked (lowercase) blocks. Sequences can be read
+//! partially without decoding the whole file.
+//!
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+use ::dna::Dna;
+use super::{Error, Result};
+use super::fasta::Record;
+
+const SIGNATURE: u32 = 0x1A41_2743;
+
+/// Bases in the order of their 2bit codes.
+const BASES: &[u8; 4] = b"TCAG";
+
+fn base_code(b: u8) -> u8 {
+    match b.to_ascii_uppercase() {
+        b'T' => 0,
+        b'C' => 1,
+        b'A' => 2,
+        b'G' => 3,
+        _ => 0,
+    }
+}
+
+/// Returns `(start, length)` blocks of consecutive positions matching `p`.
+fn blocks<F: Fn(u8) -> bool>(seq: &[u8], p: F) -> Vec<(u32, u32)> {
+    let mut res = Vec::new();
+    let mut start = None;
+    for (i, &b) in seq.iter().enumerate() {
+        match (p(b), start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                res.push((s as u32, (i - s) as u32));
+                start = None;
+            },
+            _ => (),
+        }
+    }
+    if let Some(s) = start {
+        res.push((s as u32, (seq.len() - s) as u32));
+    }
+    res
+}
+
+/// Writes `records` in 2bit format. Bases other than `A`, `C`, `G` and
+/// `T` are stored as N-blocks, lowercase bases as soft-mask blocks, so
+/// read FASTA with `preserve_case` to keep the masking.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Cursor;
+/// use bio::data::fasta::Record;
+/// use bio::data::twobit;
+/// use bio::dna::Dna;
+///
+/// let records = vec![Record::new("chr1", None, Dna::from_str("ACGTNNacgtAC"))];
+/// let mut out = Vec::new();
+/// twobit::write(&mut out, &records).unwrap();
+///
+/// let mut reader = twobit::Reader::new(Cursor::new(out)).unwrap().preserve_case();
+/// assert_eq!(reader.len("chr1"), Some(12));
+/// assert_eq!(reader.read_range("chr1", 3, 9).unwrap().as_str(), "TNNacg");
+/// ```
+pub fn write<W: Write>(mut writer: W, records: &[Record]) -> io::Result<()> {
+    let too_large = || io::Error::new(io::ErrorKind::InvalidInput, "2bit file exceeds 4 GB");
+    let mut index_size = 16u64;
+    for record in records.iter() {
+        if record.id.len() > 255 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "2bit sequence name is too long"));
+        }
+        index_size += 1 + record.id.len() as u64 + 4;
+    }
+
+    let mut headers = Vec::with_capacity(records.len());
+    let mut offset = index_size;
+    for record in records.iter() {
+        let seq = &record.seq;
+        let n_blocks = blocks(seq, |b| !b"ACGTacgt".contains(&b));
+        let mask_blocks = blocks(seq, |b| b.is_ascii_lowercase());
+        let size = 4 * (4 + 2 * n_blocks.len() + 2 * mask_blocks.len()) as u64 + (seq.len() as u64).div_ceil(4);
+        if seq.len() as u64 > u64::from(u32::MAX) || offset > u64::from(u32::MAX) {
+            return Err(too_large());
+        }
+        headers.push((offset, n_blocks, mask_blocks));
+        offset += size;
+    }
+    if offset > u64::from(u32::MAX) + 1 {
+        return Err(too_large());
+    }
+
+    for &v in [SIGNATURE, 0, records.len() as u32, 0].iter() {
+        writer.write_all(&v.to_le_bytes())?;
+    }
+    for (record, &(offset, _, _)) in records.iter().zip(headers.iter()) {
+        writer.write_all(&[record.id.len() as u8])?;
+        writer.write_all(record.id.as_bytes())?;
+        writer.write_all(&(offset as u32).to_le_bytes())?;
+    }
+
+    for (record, (_, n_blocks, mask_blocks)) in records.iter().zip(headers) {
+        writer.write_all(&(record.seq.len() as u32).to_le_bytes())?;
+        for blocks in [n_blocks, mask_blocks].iter() {
+            writer.write_all(&(blocks.len() as u32).to_le_bytes())?;
+            for &(start, _) in blocks.iter() {
+                writer.write_all(&start.to_le_bytes())?;
+            }
+            for &(_, len) in blocks.iter() {
+                writer.write_all(&len.to_le_bytes())?;
+            }
+        }
+        writer.write_all(&0u32.to_le_bytes())?;
+        let packed: Vec<u8> = record.seq.chunks(4)
+            .map(|bases| {
+                let mut byte = 0;
+                for (i, &b) in bases.iter().enumerate() {
+                    byte |= base_code(b) << (6 - 2 * i);
+                }
+                byte
+            })
+            .collect();
+        writer.write_all(&packed)?;
+    }
+    Ok(())
+}
+
+/// Header of a sequence record.
+struct SeqHeader {
+    len: usize,
+    n_blocks: Vec<(usize, usize)>,
+    mask_blocks: Vec<(usize, usize)>,
+    /// File offset of the packed bases.
+    dna_offset: u64,
+}
+
+/// Random-access reader of a 2bit file.
+pub struct Reader<R> {
+    reader: R,
+    big_endian: bool,
+    names: Vec<String>,
+    offsets: HashMap<String, u64>,
+    headers: HashMap<String, SeqHeader>,
+    uppercase: bool,
+}
+
+impl<R: Read + Seek> Reader<R> {
+
+    /// Reads the file header and the sequence index.
+    pub fn new(reader: R) -> Result<Reader<R>> {
+        let mut reader = Reader {
+            reader,
+            big_endian: false,
+            names: Vec::new(),
+            offsets: HashMap::new(),
+            headers: HashMap::new(),
+            uppercase: true,
+        };
+        reader.reader.seek(SeekFrom::Start(0))?;
+        let signature = reader.read_u32()?;
+        if signature != SIGNATURE {
+            if signature.swap_bytes() != SIGNATURE {
+                return Err(Error::binary(0, "invalid 2bit signature"));
+            }
+            reader.big_endian = true;
+        }
+        if reader.read_u32()? != 0 {
+            return Err(Error::binary(4, "unsupported 2bit version"));
+        }
+        let count = reader.read_u32()?;
+        reader.read_u32()?;
+
+        for _ in 0..count {
+            let mut len = [0u8];
+            reader.reader.read_exact(&mut len)?;
+            let name_offset = reader.reader.stream_position()?;
+            let mut name = vec![0u8; len[0] as usize];
+            reader.reader.read_exact(&mut name)?;
+            let name = String::from_utf8(name)
+                .map_err(|_| Error::binary(name_offset, "sequence name is not valid utf-8"))?;
+            let offset = reader.read_u32()?;
+            reader.names.push(name.clone());
+            reader.offsets.insert(name, u64::from(offset));
+        }
+        Ok(reader)
+    }
+
+    /// Keep lowercase (soft-masked) bases as is.
+    pub fn preserve_case(mut self) -> Reader<R> {
+        self.uppercase = false;
+        self
+    }
+
+    /// Sequence names in the file order.
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Length of the sequence `name`.
+    pub fn len(&mut self, name: &str) -> Option<usize> {
+        self.header(name).ok().map(|h| h.len)
+    }
+
+    /// Reads the whole sequence `name`.
+    pub fn read(&mut self, name: &str) -> Result<Dna> {
+        let len = self.header(name)?.len;
+        self.read_range(name, 0, len)
+    }
+
+    /// Reads bases `start..end` of the sequence `name`, decoding only the
+    /// bytes covering the range.
+    pub fn read_range(&mut self, name: &str, start: usize, end: usize) -> Result<Dna> {
+        let (len, dna_offset) = {
+            let header = self.header(name)?;
+            (header.len, header.dna_offset)
+        };
+        if start > end || end > len {
+            let message = format!("range {}..{} is out of sequence '{}' bounds", start, end, name);
+            return Err(Error::binary(dna_offset, &message));
+        }
+
+        let first = start / 4;
+        let mut packed = vec![0u8; end.div_ceil(4) - first];
+        self.reader.seek(SeekFrom::Start(dna_offset + first as u64))?;
+        self.reader.read_exact(&mut packed)?;
+        let mut seq: Vec<u8> = (start..end)
+            .map(|i| {
+                let byte = packed[i / 4 - first];
+                BASES[((byte >> (6 - 2 * (i % 4))) & 3) as usize]
+            })
+            .collect();
+
+        let header = &self.headers[name];
+        for &(block_start, block_len) in header.n_blocks.iter() {
+            for i in block_start.max(start)..(block_start + block_len).min(end) {
+                seq[i - start] = b'N';
+            }
+        }
+        if !self.uppercase {
+            for &(block_start, block_len) in header.mask_blocks.iter() {
+                for i in block_start.max(start)..(block_start + block_len).min(end) {
+                    seq[i - start].make_ascii_lowercase();
+                }
+            }
+        }
+        Ok(Dna::new(seq))
+    }
+
+    fn header(&mut self, name: &str) -> Result<&SeqHeader> {
+        if !self.headers.contains_key(name) {
+            let offset = match self.offsets.get(name) {
+                Some(&offset) => offset,
+                None => return Err(Error::not_found(name)),
+            };
+            self.reader.seek(SeekFrom::Start(offset))?;
+            let len = self.read_u32()? as usize;
+            let n_blocks = self.read_blocks()?;
+            let mask_blocks = self.read_blocks()?;
+            self.read_u32()?;
+            let dna_offset = self.reader.stream_position()?;
+            let header = SeqHeader { len, n_blocks, mask_blocks, dna_offset };
+            self.headers.insert(name.to_owned(), header);
+        }
+        Ok(&self.headers[name])
+    }
+
+    fn read_blocks(&mut self) -> Result<Vec<(usize, usize)>> {
+        // the count is not trusted for preallocation, a corrupt file would
+        // fail on reading past its end instead of allocating gigabytes
+        let count = self.read_u32()? as usize;
+        let mut starts = Vec::new();
+        for _ in 0..count {
+            starts.push(self.read_u32()? as usize);
+        }
+        let mut blocks = Vec::with_capacity(starts.len());
+        for start in starts {
+            blocks.push((start, self.read_u32()? as usize));
+        }
+        Ok(blocks)
+    }
+
+    fn read_u32(&mut self) -> Result<u32> {
+        let mut buf = [0u8; 4];
+        self.reader.read_exact(&mut buf)?;
+        if self.big_endian {
+            Ok(u32::from_be_bytes(buf))
+        } else {
+            Ok(u32::from_le_bytes(buf))
+        }
+    }
+
+}
+
+impl Reader<BufReader<File>> {
+
+    /// Opens 2bit file at `path`.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Reader<BufReader<File>>> {
+        let path = path.as_ref();
+        let file = File::open(path).map_err(|e| Error::from(e).with_path(path))?;
+        Reader::new(BufReader::new(file)).map_err(|e| e.with_path(path))
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::io::Cursor;
+
+    use super::{Reader, write};
+    use data::fasta::Record;
+    use dna::Dna;
+
+    #[test]
+    fn test_roundtrip() {
+        let records = vec![
+            Record::new("chr1", None, Dna::from_str("NNACGTacgtNACGTAGGCTTAnn")),
+            Record::new("chrM", None, Dna::from_str("GATTACA")),
+            Record::new("empty", None, Dna::from_str("")),
+        ];
+        let mut out = Vec::new();
+        write(&mut out, &records).unwrap();
+
+        let mut reader = Reader::new(Cursor::new(out.clone())).unwrap().preserve_case();
+        assert_eq!(reader.names(), ["chr1", "chrM", "empty"]);
+        for record in records.iter() {
+            assert_eq!(reader.read(&record.id).unwrap(), record.seq);
+        }
+        for start in 0..24 {
+            for end in start..25 {
+                let range = reader.read_range("chr1", start, end).unwrap();
+                assert_eq!(&range[..], &records[0].seq[start..end]);
+            }
+        }
+        assert!(reader.read_range("chr1", 3, 25).is_err());
+        let err = reader.read("chrX").unwrap_err();
+        assert_eq!(err.to_string(), "no record 'chrX'");
+
+        let mut reader = Reader::new(Cursor::new(out)).unwrap();
+        assert_eq!(reader.read("chr1").unwrap().as_str(), "NNACGTACGTNACGTAGGCTTANN");
+    }
+
+    #[test]
+    fn test_truncated() {
+        let records = vec![Record::new("chr1", None, Dna::from_str("ACGTNNAC"))];
+        let mut out = Vec::new();
+        write(&mut out, &records).unwrap();
+        // N-block count of the only sequence, right after its length
+        let at = 16 + 1 + 4 + 4 + 4;
+        out[at..at + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+        out.truncate(at + 8);
+        let mut reader = Reader::new(Cursor::new(out)).unwrap();
+        assert!(reader.read("chr1").is_err());
+    }
+
+    #[test]
+    fn test_invalid_signature() {
+        let reader = Reader::new(Cursor::new(vec![0u8; 16]));
+        assert!(reader.is_err());
+    }
+
+}
@@ -0,0 +1,306 @@
+//! Composition statistics of DNA sequences: GC content, base and
+//! dinucleotide frequencies, odds ratios and entropy, over whole
+//! sequences or sliding windows.
+//!
+//! # Examples
+//!
+//! Windows with CpG observed/expected ratio above 0.6 and GC content above
+//! 0.5, as CpG island candidates:
+//!
+//! ```
+//! use bio::dna::composition::{self, Composition};
+//!
+//! let seq = b"ATATTATATACGCGCGGCGCATATATTAT";
+//! let islands: Vec<_> = composition::windows(seq, 10, 5)
+//!     .filter(|&(_, ref c)| c.gc_content() > 0.5 && c.cpg_ratio() > 0.6)
+//!     .map(|(start, _)| start)
+//!     .collect();
+//! assert_eq!(islands, [10]);
+//! ```
+
+use ::alphabet::{Alphabet, DnaAlphabet};
+
+/// `DnaAlphabet` rank of nucleotide `nuc`, lowercase (soft-masked)
+/// nucleotides included.
+fn index(nuc: u8) -> Option<usize> {
+    DnaAlphabet::rank(nuc.to_ascii_uppercase())
+}
+
+/// Index of nucleotide `nuc` given as an argument.
+///
+/// # Panics
+///
+/// Panics if `nuc` is not one of `A`, `C`, `G` and `T`.
+fn expect_index(nuc: u8) -> usize {
+    index(nuc).unwrap_or_else(|| panic!("Unsupported nucleotide {}", nuc as char))
+}
+
+/// `x / y`, or 0 if `y` is 0.
+fn ratio(x: f64, y: f64) -> f64 {
+    if y > 0. { x / y } else { 0. }
+}
+
+/// Counts of bases and dinucleotides of a sequence. Lowercase bases are
+/// counted as uppercase ones. Other bytes, such as `N`, are counted
+/// separately and break dinucleotides.
+///
+/// Frequencies and ratios are 0 when there is nothing to count.
+///
+/// # Examples
+///
+/// ```
+/// use bio::dna::Dna;
+/// use bio::dna::composition::Composition;
+///
+/// let dna = Dna::from_str("ACGCGTNA");
+/// let c = Composition::new(&dna);
+/// assert_eq!(c.count(b'C'), 2);
+/// assert_eq!(c.other(), 1);
+/// assert_eq!(c.dinucleotide_count(b'C', b'G'), 2);
+/// assert_eq!(c.gc_content(), 4. / 7.);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Composition {
+    bases: [usize; 4],
+    other: usize,
+    pairs: [[usize; 4]; 4],
+}
+
+impl Composition {
+
+    pub fn new(seq: &[u8]) -> Composition {
+        let mut c = Composition::default();
+        for i in 0..seq.len() {
+            c.add(seq, i);
+        }
+        c
+    }
+
+    /// Counts base `i` of `seq` and the dinucleotide it ends.
+    fn add(&mut self, seq: &[u8], i: usize) {
+        let pair = if i > 0 { Some((seq[i - 1], seq[i])) } else { None };
+        self.update(seq[i], pair, |n| *n += 1)
+    }
+
+    /// Uncounts base `i` of `seq` and the dinucleotide it starts.
+    fn remove(&mut self, seq: &[u8], i: usize) {
+        let pair = seq.get(i + 1).map(|&next| (seq[i], next));
+        self.update(seq[i], pair, |n| *n -= 1)
+    }
+
+    fn update<F: Fn(&mut usize)>(&mut self, base: u8, pair: Option<(u8, u8)>, f: F) {
+        match index(base) {
+            Some(x) => f(&mut self.bases[x]),
+            None => f(&mut self.other),
+        }
+        if let Some((a, b)) = pair {
+            if let (Some(x), Some(y)) = (index(a), index(b)) {
+                f(&mut self.pairs[x][y]);
+            }
+        }
+    }
+
+    /// Number of counted bytes, `A`, `C`, `G`, `T` and others.
+    pub fn len(&self) -> usize {
+        self.bases() + self.other
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of `A`, `C`, `G` and `T`.
+    pub fn bases(&self) -> usize {
+        self.bases.iter().sum()
+    }
+
+    /// Number of bytes other than `A`, `C`, `G` and `T`.
+    pub fn other(&self) -> usize {
+        self.other
+    }
+
+    /// Number of `nuc` nucleotides.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `nuc` is not one of `A`, `C`, `G` and `T`.
+    pub fn count(&self, nuc: u8) -> usize {
+        self.bases[expect_index(nuc)]
+    }
+
+    /// Number of occurrences of dinucleotide `ab`.
+    pub fn dinucleotide_count(&self, a: u8, b: u8) -> usize {
+        self.pairs[expect_index(a)][expect_index(b)]
+    }
+
+    /// Number of dinucleotides of `A`, `C`, `G` and `T`.
+    pub fn dinucleotides(&self) -> usize {
+        self.pairs.iter().map(|row| row.iter().sum::<usize>()).sum()
+    }
+
+    /// Fraction of `G` and `C` among `A`, `C`, `G` and `T`.
+    pub fn gc_content(&self) -> f64 {
+        ratio((self.bases[1] + self.bases[2]) as f64, self.bases() as f64)
+    }
+
+    /// Frequency of `nuc` among `A`, `C`, `G` and `T`.
+    pub fn frequency(&self, nuc: u8) -> f64 {
+        ratio(self.count(nuc) as f64, self.bases() as f64)
+    }
+
+    /// Frequencies of `A`, `C`, `G` and `T`, in this order.
+    pub fn frequencies(&self) -> [f64; 4] {
+        let mut freqs = [0.; 4];
+        for (f, &n) in freqs.iter_mut().zip(self.bases.iter()) {
+            *f = ratio(n as f64, self.bases() as f64);
+        }
+        freqs
+    }
+
+    /// Frequency of dinucleotide `ab` among all dinucleotides.
+    pub fn dinucleotide_frequency(&self, a: u8, b: u8) -> f64 {
+        ratio(self.dinucleotide_count(a, b) as f64, self.dinucleotides() as f64)
+    }
+
+    /// Dinucleotide odds ratio, observed frequency of `ab` over the one
+    /// expected from the frequencies of `a` and `b`. Values far from 1
+    /// indicate over- or under-representation.
+    pub fn odds_ratio(&self, a: u8, b: u8) -> f64 {
+        let expected = self.frequency(a) * self.frequency(b);
+        ratio(self.dinucleotide_frequency(a, b), expected)
+    }
+
+    /// CpG observed/expected ratio, the odds ratio of `CG`.
+    pub fn cpg_ratio(&self) -> f64 {
+        self.odds_ratio(b'C', b'G')
+    }
+
+    /// Shannon entropy of base frequencies in bits, from 0 for a single
+    /// repeated base to 2 for equal frequencies.
+    pub fn entropy(&self) -> f64 {
+        -self.frequencies().iter()
+            .filter(|&&p| p > 0.)
+            .map(|&p| p * p.log2())
+            .sum::<f64>()
+    }
+
+}
+
+/// Compositions of windows of length `size` starting every `step` bases,
+/// with their start positions. Only windows fully inside `seq` are
+/// yielded, counts are updated incrementally as the window slides.
+///
+/// # Panics
+///
+/// Panics if `size` or `step` is 0.
+pub fn windows(seq: &[u8], size: usize, step: usize) -> Windows<'_> {
+    assert!(size > 0 && step > 0, "window size and step must be positive");
+    Windows { seq, size, step, start: 0, composition: None }
+}
+
+/// Iterator over window compositions, see `windows`.
+pub struct Windows<'a> {
+    seq: &'a [u8],
+    size: usize,
+    step: usize,
+    /// Start of the next window.
+    start: usize,
+    /// Composition of the previous window.
+    composition: Option<Composition>,
+}
+
+impl<'a> Iterator for Windows<'a> {
+    type Item = (usize, Composition);
+
+    fn next(&mut self) -> Option<(usize, Composition)> {
+        let start = self.start;
+        if start + self.size > self.seq.len() {
+            return None;
+        }
+        let composition = match self.composition.take() {
+            Some(mut c) if self.step < self.size => {
+                // uncount bases leaving the window, count the entering ones
+                let prev = start - self.step;
+                let seq = &self.seq[prev..start + self.size];
+                for i in 0..self.step {
+                    c.remove(&seq[..self.size], i);
+                    c.add(&seq[self.step..], self.size - self.step + i);
+                }
+                c
+            }
+            _ => Composition::new(&self.seq[start..start + self.size]),
+        };
+        self.start += self.step;
+        self.composition = Some(composition.clone());
+        Some((start, composition))
+    }
+}
+
+/// Series of `stat` of windows, see `windows`, as pairs of window start
+/// and value for plotting or thresholding.
+///
+/// # Examples
+///
+/// ```
+/// use bio::dna::composition::{self, Composition};
+///
+/// let series = composition::series(b"AAAAGGGG", 4, 2, Composition::gc_content);
+/// assert_eq!(series, [(0, 0.), (2, 0.5), (4, 1.)]);
+/// ```
+pub fn series<F>(seq: &[u8], size: usize, step: usize, stat: F) -> Vec<(usize, f64)>
+    where F: Fn(&Composition) -> f64,
+{
+    windows(seq, size, step)
+        .map(|(start, c)| (start, stat(&c)))
+        .collect()
+}
+
+/// GC content of windows, see `series`.
+pub fn gc_content_series(seq: &[u8], size: usize, step: usize) -> Vec<(usize, f64)> {
+    series(seq, size, step, Composition::gc_content)
+}
+
+/// CpG observed/expected ratio of windows, see `series`.
+pub fn cpg_ratio_series(seq: &[u8], size: usize, step: usize) -> Vec<(usize, f64)> {
+    series(seq, size, step, Composition::cpg_ratio)
+}
+
+/// Shannon entropy of windows, see `series`.
+pub fn entropy_series(seq: &[u8], size: usize, step: usize) -> Vec<(usize, f64)> {
+    series(seq, size, step, Composition::entropy)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{windows, Composition};
+
+    #[test]
+    fn test_composition() {
+        let c = Composition::new(b"ACGTacgtNNCG");
+        assert_eq!((c.len(), c.bases(), c.other()), (12, 10, 2));
+        assert_eq!(c.frequencies(), [0.2, 0.3, 0.3, 0.2]);
+        assert_eq!(c.dinucleotides(), 8);
+        assert_eq!(c.dinucleotide_count(b'C', b'G'), 3);
+        assert_eq!(c.dinucleotide_count(b'T', b'A'), 1);
+        assert!((c.cpg_ratio() - (3. / 8.) / (0.3 * 0.3)).abs() < 1e-12);
+        assert_eq!(Composition::new(b"ACGT").entropy(), 2.);
+        assert_eq!(Composition::new(b"AAAA").entropy(), 0.);
+        assert_eq!(Composition::new(b"NNN").gc_content(), 0.);
+    }
+
+    #[test]
+    fn test_windows() {
+        let seq = b"ACGTTGCANNAGTCCACGCGGATCaacgtt";
+        for &(size, step) in [(1, 1), (5, 1), (6, 4), (7, 7), (4, 9), (30, 1), (31, 1)].iter() {
+            let slid: Vec<_> = windows(seq, size, step).collect();
+            let expected: Vec<_> = (0..seq.len())
+                .step_by(step)
+                .filter(|&i| i + size <= seq.len())
+                .map(|i| (i, Composition::new(&seq[i..i + size])))
+                .collect();
+            assert_eq!(slid, expected, "size {} step {}", size, step);
+        }
+    }
+
+}
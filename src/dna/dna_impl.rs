@@ -1,6 +1,7 @@
 //! DNA implementation based on u8 vector.
 
 use std::ascii::AsciiExt;
+use std::error;
 use std::fmt;
 use std::iter::IntoIterator;
 use std::ops;
@@ -8,6 +9,9 @@ use std::slice;
 use std::str;
 use std::vec;
 
+use super::iupac;
+use ::seq::{self, Circular, KmpMatches};
+
 pub const A: u8 = b'A';
 pub const T: u8 = b'T';
 pub const G: u8 = b'G';
@@ -45,10 +49,73 @@ impl Dna {
         Dna { vec: s.to_vec() }
     }
 
+    /// Parses DNA string, every character must be one of `A`, `C`, `G`
+    /// and `T`.
     pub fn parse_from_str(s: &str) -> Result<Dna, AsciiError> {
         str::FromStr::from_str(s)
     }
 
+    /// Creates `Dna` from bytes, checking that every byte is one of `A`,
+    /// `C`, `G` and `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bio::dna::{AsciiErrorKind, Dna};
+    ///
+    /// let err = Dna::parse_from_slice(b"ACGX").unwrap_err();
+    /// assert_eq!(err.valid_up_to(), 3);
+    /// assert_eq!(err.byte(), b'X');
+    /// assert_eq!(err.kind(), AsciiErrorKind::InvalidNucleotide);
+    /// ```
+    pub fn parse_from_slice(s: &[u8]) -> Result<Dna, AsciiError> {
+        Dna::parse_bytes(s, false, is_nucleotide)
+    }
+
+    /// Creates `Dna` from bytes which may contain soft-masked (lowercase)
+    /// bases, they are converted to uppercase.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bio::dna::Dna;
+    ///
+    /// let dna = Dna::parse_masked(b"ACgtA").unwrap();
+    /// assert_eq!(dna.as_str(), "ACGTA");
+    /// assert!(Dna::parse_masked(b"ACGT\n").is_err());
+    /// ```
+    pub fn parse_masked(s: &[u8]) -> Result<Dna, AsciiError> {
+        Dna::parse_bytes(s, true, is_nucleotide)
+    }
+
+    /// Creates `Dna` from bytes, checking that every byte is an uppercase
+    /// IUPAC nucleotide code, such as `N` or `W`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bio::dna::Dna;
+    ///
+    /// let motif = Dna::parse_iupac(b"TTWTCCACA").unwrap();
+    /// assert_eq!(motif.reverse_complement().as_str(), "TGTGGAWAA");
+    /// assert!(Dna::parse_iupac(b"TTXT").is_err());
+    /// ```
+    pub fn parse_iupac(s: &[u8]) -> Result<Dna, AsciiError> {
+        Dna::parse_bytes(s, false, iupac::is_valid)
+    }
+
+    fn parse_bytes(s: &[u8], uppercase: bool, valid: fn(u8) -> bool) -> Result<Dna, AsciiError> {
+        let mut vec = Vec::with_capacity(s.len());
+        for (i, &b) in s.iter().enumerate() {
+            let nuc = if uppercase { b.to_ascii_uppercase() } else { b };
+            if !nuc.is_ascii_uppercase() || !valid(nuc) {
+                return Err(AsciiError::new(i, b));
+            }
+            vec.push(nuc);
+        }
+        Ok(Dna::new(vec))
+    }
+
     pub fn from_str(s: &str) -> Dna {
         let bytes = s.as_bytes();
         Dna::from_slice(bytes)
@@ -72,7 +139,43 @@ impl Dna {
         &self[i..i+k]
     }
 
-    /// Make complement for this DNA string
+    /// Circular view of this DNA string, for circular genomes where k-mers
+    /// may span the end of the sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bio::dna::Dna;
+    ///
+    /// let dna = Dna::from_str("GATTACA");
+    /// assert_eq!(&dna.circular().window(5, 4)[..], b"CAGA");
+    /// ```
+    pub fn circular(&self) -> Circular<'_, u8> {
+        Circular::new(&self.vec)
+    }
+
+    /// Start positions of all, possibly overlapping, occurrences of
+    /// `pattern` in this DNA string, see `seq::find`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bio::dna::Dna;
+    ///
+    /// let dna = Dna::from_str("GATATATGCATATAC");
+    /// assert_eq!(dna.find(b"ATAT"), [1, 3, 9]);
+    /// ```
+    pub fn find(&self, pattern: &[u8]) -> Vec<usize> {
+        seq::find(&self.vec, pattern)
+    }
+
+    /// Lazy version of `find`.
+    pub fn find_iter<'a>(&'a self, pattern: &'a [u8]) -> KmpMatches<'a, u8> {
+        seq::find_iter(&self.vec, pattern)
+    }
+
+    /// Make complement for this DNA string, IUPAC ambiguity codes are
+    /// complemented too.
     ///
     /// # Examples
     ///
@@ -113,13 +216,11 @@ impl Dna {
 // Utilities
 
 fn complement(nuc: u8) -> u8 {
-    match nuc {
-        A => T,
-        T => A,
-        G => C,
-        C => G,
-        x => x,
-    }
+    iupac::complement(nuc)
+}
+
+fn is_nucleotide(nuc: u8) -> bool {
+    matches!(nuc, A | C | G | T)
 }
 
 impl Eq for Dna {}
@@ -170,15 +271,7 @@ impl str::FromStr for Dna {
     type Err = AsciiError;
 
     fn from_str(s: &str) -> Result<Dna, AsciiError> {
-        if s.is_ascii() {
-            Ok(Dna::from_slice(s.as_bytes()))
-        } else {
-            let (last_valid, _) = s.char_indices()
-                .take_while(|&(_, c)| c.is_ascii())
-                .last()
-                .unwrap();
-            Err(AsciiError { valid_up_to: last_valid })
-        }
+        Dna::parse_from_slice(s.as_bytes())
     }
 }
 
@@ -200,16 +293,31 @@ impl<'a> IntoIterator for &'a Dna {
     }
 }
 
+/// Kind of the byte rejected by `Dna` parsing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AsciiErrorKind {
+    /// Byte is not ASCII, such as a part of multibyte UTF-8 character.
+    NonAscii,
+    /// ASCII character which is not an allowed nucleotide.
+    InvalidNucleotide,
+}
+
 /// Errors which can occur when attempting to interpret a sequence of
 /// `u8` as `Dna`.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AsciiError {
     valid_up_to: usize,
+    byte: u8,
 }
 
 impl AsciiError {
-    /// Returns the index in the given string up to which valid Ascii
-    /// was verified.
+
+    pub(crate) fn new(valid_up_to: usize, byte: u8) -> AsciiError {
+        AsciiError { valid_up_to, byte }
+    }
+
+    /// Returns the index in the given string up to which valid DNA
+    /// was verified, which is the index of the offending byte.
     ///
     /// It is the maximum index such that `parse_from_str(input[..index])`
     /// would return Ok(_).
@@ -217,21 +325,42 @@ impl AsciiError {
     /// # Examples
     ///
     /// ```
-    /// use bio::dna::Dna;
+    /// use bio::dna::{AsciiErrorKind, Dna};
     ///
     /// let s = "AAT©";
     /// let err = Dna::parse_from_str(s).unwrap_err();
-    /// assert_eq!(2, err.valid_up_to());
+    /// assert_eq!(3, err.valid_up_to());
+    /// assert_eq!(err.kind(), AsciiErrorKind::NonAscii);
     /// ```
     pub fn valid_up_to(&self) -> usize { self.valid_up_to }
+
+    /// The offending byte.
+    pub fn byte(&self) -> u8 { self.byte }
+
+    pub fn kind(&self) -> AsciiErrorKind {
+        if self.byte.is_ascii() {
+            AsciiErrorKind::InvalidNucleotide
+        } else {
+            AsciiErrorKind::NonAscii
+        }
+    }
+
 }
 
 impl fmt::Display for AsciiError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "invalid ascii: invalid byte near index {}", self.valid_up_to)
+        match self.kind() {
+            AsciiErrorKind::NonAscii =>
+                write!(f, "invalid ascii: invalid byte 0x{:02x} at index {}", self.byte, self.valid_up_to),
+            AsciiErrorKind::InvalidNucleotide =>
+                write!(f, "invalid nucleotide '{}' at index {}",
+                       (self.byte as char).escape_default(), self.valid_up_to),
+        }
     }
 }
 
+impl error::Error for AsciiError {}
+
 #[cfg(test)]
 mod tests {
 
@@ -258,6 +387,27 @@ mod tests {
         assert_eq!(SAMPLE, dna.as_str());
     }
 
+    #[test]
+    fn test_parse() {
+        assert_eq!(Dna::parse_from_str(SAMPLE).unwrap().as_str(), SAMPLE);
+        assert_eq!(Dna::parse_from_slice(b"").unwrap().len(), 0);
+
+        let err = Dna::parse_from_slice(b"ACgt").unwrap_err();
+        assert_eq!((err.valid_up_to(), err.byte()), (2, b'g'));
+        let err = Dna::parse_masked(b"acgn").unwrap_err();
+        assert_eq!((err.valid_up_to(), err.byte()), (3, b'n'));
+        let err = "ACG\nT".parse::<Dna>().unwrap_err();
+        assert_eq!(err.to_string(), "invalid nucleotide '\\n' at index 3");
+    }
+
+    #[test]
+    fn test_iupac() {
+        let dna = Dna::parse_iupac(b"ACGTNRYKMSWBDHV").unwrap();
+        assert_eq!(dna.reverse_complement().as_str(), "BDHVWSKMRYNACGT");
+        let err = Dna::parse_iupac(b"ACGTn").unwrap_err();
+        assert_eq!((err.valid_up_to(), err.byte()), (4, b'n'));
+    }
+
     #[test]
     fn test_reverse_complement() {
         let mut dna = Dna::from_str("AAAACCCGGT");
@@ -0,0 +1,213 @@
+//! Low-complexity regions, such as poly-A runs and short tandem repeats,
+//! detected with the DUST algorithm and masked.
+//!
+//! Masked bases are lowercase (soft-masked) or `N` (hard-masked). K-mers
+//! with masked bases are skipped by `Kmer::iter`, the k-mer counting
+//! functions of `strings` and the motif searchers of `dna`.
+
+use std::ops::Range;
+
+use ::alphabet::{Alphabet, DnaAlphabet};
+
+/// Window length of the original DUST.
+pub const DEFAULT_WINDOW: usize = 64;
+
+/// Score threshold, the level 20 of the original DUST.
+pub const DEFAULT_THRESHOLD: f64 = 2.0;
+
+/// Code of the triplet of uppercase nucleotides, `None` if any of them is
+/// not one of `A`, `C`, `G` and `T`.
+fn triplet(s: &[u8]) -> Option<usize> {
+    s.iter().try_fold(0, |acc, &nuc| {
+        DnaAlphabet::rank(nuc.to_ascii_uppercase()).map(|code| acc * 4 + code)
+    })
+}
+
+/// Returns `true` if `nuc` is soft-masked (lowercase) or hard-masked
+/// (`N`).
+pub fn is_masked(nuc: u8) -> bool {
+    nuc.is_ascii_lowercase() || nuc == b'N'
+}
+
+/// Returns `true` if any base of `kmer` is masked.
+pub fn has_masked(kmer: &[u8]) -> bool {
+    kmer.iter().any(|&nuc| is_masked(nuc))
+}
+
+/// Low-complexity region detection with the DUST algorithm.
+///
+/// A region scores `sum(c_t * (c_t - 1) / 2) / (l - 1)`, where `c_t` is the
+/// number of occurrences of triplet `t` among the `l` triplets of the
+/// region. Windows of `window` bases, starting every `window / 2` bases,
+/// are searched for their best scoring region, which is reported if its
+/// score exceeds `threshold`, and then the rest of the window on both
+/// sides of the region is searched the same way. Lowercase bases are
+/// scored as uppercase ones, triplets with other bytes, such as `N`, split
+/// regions.
+///
+/// # Examples
+///
+/// ```
+/// use bio::dna::Dna;
+/// use bio::dna::dust::Dust;
+///
+/// let mut dna = Dna::from_str("GATCCTAGGCAAAAAAAAAAAAAGTTCAGCTAGCAT");
+/// assert_eq!(Dust::new().regions(&dna), [10..23]);
+///
+/// Dust::new().soft_mask(&mut dna);
+/// assert_eq!(dna.as_str(), "GATCCTAGGCaaaaaaaaaaaaaGTTCAGCTAGCAT");
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Dust {
+    window: usize,
+    threshold: f64,
+}
+
+impl Default for Dust {
+    fn default() -> Dust {
+        Dust::new()
+    }
+}
+
+impl Dust {
+
+    pub fn new() -> Dust {
+        Dust { window: DEFAULT_WINDOW, threshold: DEFAULT_THRESHOLD }
+    }
+
+    /// Window length, `DEFAULT_WINDOW` by default.
+    pub fn window(mut self, window: usize) -> Dust {
+        assert!(window >= 4, "DUST window should be at least 4 bases");
+        self.window = window;
+        self
+    }
+
+    /// Score threshold, `DEFAULT_THRESHOLD` by default. Lower thresholds
+    /// mask more.
+    pub fn threshold(mut self, threshold: f64) -> Dust {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Sorted, non-overlapping low-complexity regions of `seq`.
+    pub fn regions(&self, seq: &[u8]) -> Vec<Range<usize>> {
+        let mut found = Vec::new();
+        let step = self.window / 2;
+        let mut start = 0;
+        while start + 3 <= seq.len() {
+            let end = (start + self.window).min(seq.len());
+            self.search(seq, start..end, &mut found);
+            if end == seq.len() {
+                break;
+            }
+            start += step;
+        }
+
+        found.sort_by_key(|r| r.start);
+        let mut regions: Vec<Range<usize>> = Vec::with_capacity(found.len());
+        for r in found {
+            match regions.last_mut() {
+                Some(last) if last.end >= r.start => last.end = last.end.max(r.end),
+                _ => regions.push(r),
+            }
+        }
+        regions
+    }
+
+    /// Pushes the best scoring region of `seq[range]` to `found`, then
+    /// searches the rest of the range on both sides of it.
+    fn search(&self, seq: &[u8], range: Range<usize>, found: &mut Vec<Range<usize>>) {
+        if let Some(r) = self.best_region(&seq[range.clone()]) {
+            let r = range.start + r.start..range.start + r.end;
+            self.search(seq, range.start..r.start, found);
+            self.search(seq, r.end..range.end, found);
+            found.push(r);
+        }
+    }
+
+    /// Best scoring region of `window` above the threshold.
+    fn best_region(&self, window: &[u8]) -> Option<Range<usize>> {
+        let mut best = (self.threshold, None);
+        for i in 0..window.len() {
+            let mut counts = [0usize; 64];
+            let mut sum = 0;
+            for (l, s) in window[i..].windows(3).enumerate() {
+                let t = match triplet(s) {
+                    Some(t) => t,
+                    None => break,
+                };
+                sum += counts[t];
+                counts[t] += 1;
+                if l > 0 {
+                    let score = sum as f64 / l as f64;
+                    if score > best.0 {
+                        best = (score, Some(i..i + l + 3));
+                    }
+                }
+            }
+        }
+        best.1
+    }
+
+    /// Lowercases low-complexity regions of `seq`.
+    pub fn soft_mask(&self, seq: &mut [u8]) -> Vec<Range<usize>> {
+        let regions = self.regions(seq);
+        soft_mask(seq, &regions);
+        regions
+    }
+
+    /// Replaces low-complexity regions of `seq` with `N`.
+    pub fn hard_mask(&self, seq: &mut [u8]) -> Vec<Range<usize>> {
+        let regions = self.regions(seq);
+        hard_mask(seq, &regions);
+        regions
+    }
+
+}
+
+/// Lowercases `regions` of `seq`.
+pub fn soft_mask(seq: &mut [u8], regions: &[Range<usize>]) {
+    for r in regions.iter() {
+        seq[r.clone()].make_ascii_lowercase();
+    }
+}
+
+/// Replaces `regions` of `seq` with `N`.
+pub fn hard_mask(seq: &mut [u8], regions: &[Range<usize>]) {
+    for r in regions.iter() {
+        for nuc in seq[r.clone()].iter_mut() {
+            *nuc = b'N';
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{has_masked, Dust};
+
+    #[test]
+    fn test_regions() {
+        let dust = Dust::new();
+        assert!(dust.regions(b"").is_empty());
+        assert!(dust.regions(b"ACGTTGCAAGTCCTAGGATCAGTCAGGTACCATGA").is_empty());
+        // repeats separated by a complex stretch longer than a window
+        let mut seq = b"CACACACACACACACACACA".to_vec();
+        seq.extend_from_slice(b"GATCCTAGGCATGCCGTAATGGTCACTGAACGATTCGAGTCTAGCAGTTACCAGATTGCATCGGAGTTCA");
+        seq.extend_from_slice(b"TTTTTTTTTTTT");
+        assert_eq!(dust.regions(&seq), [0..20, 90..102]);
+        // lowercase bases are scored, N splits regions
+        assert_eq!(dust.regions(b"GATCaaaaaaaaaaNaaaaaaaaaaGATC"), [4..14, 15..25]);
+    }
+
+    #[test]
+    fn test_mask() {
+        let mut seq = b"GATCCTAGGCAAAAAAAAAAAAAGTTCAG".to_vec();
+        let regions = Dust::new().hard_mask(&mut seq);
+        assert_eq!(regions, [10..23]);
+        assert_eq!(&seq[..], &b"GATCCTAGGCNNNNNNNNNNNNNGTTCAG"[..]);
+        assert!(has_masked(&seq[8..12]));
+        assert!(!has_masked(&seq[..10]));
+    }
+
+}
@@ -0,0 +1,142 @@
+//! IUPAC nucleotide ambiguity codes.
+//!
+//! Besides `A`, `C`, `G` and `T` (or `U`), a code stands for a set of
+//! nucleotides, such as `W` for `A` or `T` and `N` for any nucleotide.
+//! Codes are case insensitive, so soft-masked sequences are matched too.
+
+const A: u8 = 0b0001;
+const C: u8 = 0b0010;
+const G: u8 = 0b0100;
+const T: u8 = 0b1000;
+
+/// Set of nucleotides denoted by `code` as a bit mask, `A = 1`, `C = 2`,
+/// `G = 4` and `T = 8`. Returns 0 for bytes which are not IUPAC codes.
+///
+/// # Examples
+///
+/// ```
+/// use bio::dna::iupac;
+///
+/// assert_eq!(iupac::bases(b'W'), iupac::bases(b'A') | iupac::bases(b'T'));
+/// assert_eq!(iupac::bases(b'n'), 0b1111);
+/// assert_eq!(iupac::bases(b'X'), 0);
+/// ```
+pub fn bases(code: u8) -> u8 {
+    match code.to_ascii_uppercase() {
+        b'A' => A,
+        b'C' => C,
+        b'G' => G,
+        b'T' | b'U' => T,
+        b'R' => A | G,
+        b'Y' => C | T,
+        b'S' => C | G,
+        b'W' => A | T,
+        b'K' => G | T,
+        b'M' => A | C,
+        b'B' => C | G | T,
+        b'D' => A | G | T,
+        b'H' => A | C | T,
+        b'V' => A | C | G,
+        b'N' => A | C | G | T,
+        _ => 0,
+    }
+}
+
+/// Returns `true` if `code` is an IUPAC nucleotide code.
+pub fn is_valid(code: u8) -> bool {
+    bases(code) != 0
+}
+
+/// Complement of the IUPAC code, keeping its case. Bytes which are not
+/// IUPAC codes, such as gaps, are returned as is.
+///
+/// # Examples
+///
+/// ```
+/// use bio::dna::iupac;
+///
+/// assert_eq!(iupac::complement(b'R'), b'Y');
+/// assert_eq!(iupac::complement(b'b'), b'v');
+/// assert_eq!(iupac::complement(b'-'), b'-');
+/// ```
+pub fn complement(code: u8) -> u8 {
+    let comp = match code.to_ascii_uppercase() {
+        b'A' => b'T',
+        b'T' | b'U' => b'A',
+        b'C' => b'G',
+        b'G' => b'C',
+        b'R' => b'Y',
+        b'Y' => b'R',
+        b'K' => b'M',
+        b'M' => b'K',
+        b'B' => b'V',
+        b'V' => b'B',
+        b'D' => b'H',
+        b'H' => b'D',
+        b'S' | b'W' | b'N' => code.to_ascii_uppercase(),
+        _ => return code,
+    };
+    if code.is_ascii_lowercase() { comp.to_ascii_lowercase() } else { comp }
+}
+
+/// Returns `true` if the codes `a` and `b` may denote the same nucleotide.
+#[inline]
+pub fn matches(a: u8, b: u8) -> bool {
+    bases(a) & bases(b) != 0
+}
+
+/// Returns `true` if every position of `text` matches `pattern`. Use it
+/// with `seq::find_by` to search for degenerate motifs.
+///
+/// # Examples
+///
+/// ```
+/// use bio::dna::iupac;
+/// use bio::seq;
+///
+/// let genome = b"GATTATCCACAGGTTTTCCACA";
+/// let (inds, _) = seq::find_by(genome, b"TTWTCCACA", iupac::is_match);
+/// assert_eq!(inds, [2, 13]);
+/// ```
+pub fn is_match(text: &[u8], pattern: &[u8]) -> bool {
+    text.len() == pattern.len() && text.iter().zip(pattern.iter()).all(|(&a, &b)| matches(a, b))
+}
+
+/// Number of positions of `xs` and `ys` which can not denote the same
+/// nucleotide. Use it with `seq::find_by` for approximate search.
+///
+/// # Examples
+///
+/// ```
+/// use bio::dna::iupac;
+/// use bio::seq;
+///
+/// assert_eq!(iupac::hamming_distance(b"ACGTN", b"RCGAA"), 1);
+///
+/// let (inds, _) = seq::find_by(b"ACGTTACCTA", b"ASN", |a, b| iupac::hamming_distance(a, b) == 0);
+/// assert_eq!(inds, [0, 5]);
+/// ```
+pub fn hamming_distance(xs: &[u8], ys: &[u8]) -> usize {
+    xs.iter().zip(ys.iter())
+        .filter(|&(&a, &b)| !matches(a, b))
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{bases, complement, is_valid};
+
+    #[test]
+    fn test_complement() {
+        for &code in b"ACGTRYSWKMBDHVNacgtrysw".iter() {
+            assert!(is_valid(code));
+            let comp = complement(code);
+            assert_eq!(complement(comp), code);
+            // complement of the set is the set of complements
+            let expected = bases(code).reverse_bits() >> 4;
+            assert_eq!(bases(comp), expected, "{}", code as char);
+        }
+    }
+
+}
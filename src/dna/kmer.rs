@@ -0,0 +1,295 @@
+//! DNA k-mers packed into an integer.
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use ::alphabet::{Alphabet, DnaAlphabet};
+use super::dna_impl::{AsciiError, Dna};
+use super::packed::{code, reverse_pairs};
+
+/// DNA k-mer of up to `Kmer::MAX_LEN` nucleotides, packed 2 bits per
+/// nucleotide into a `u64`.
+///
+/// The code of a k-mer is its rank among all k-mers in lexicographic
+/// order, the same number as `strings::pattern_to_number` computes, so it
+/// can index frequency arrays. K-mers are ordered lexicographically, a
+/// k-mer is less than any longer k-mer it is a prefix of.
+///
+/// # Examples
+///
+/// ```
+/// use bio::dna::Kmer;
+///
+/// let kmer = Kmer::from_slice(b"GATTC").unwrap();
+/// assert_eq!(kmer.reverse_complement().to_string(), "GAATC");
+/// assert_eq!(kmer.canonical().to_string(), "GAATC");
+/// assert!(Kmer::from_slice(b"GA").unwrap() < kmer);
+///
+/// let kmers: Vec<_> = Kmer::iter(b"ACGNTTAC", 3)
+///     .map(|(i, kmer)| (i, kmer.to_string()))
+///     .collect();
+/// assert_eq!(kmers, [(0, "ACG".to_string()), (4, "TTA".to_string()), (5, "TAC".to_string())]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Kmer {
+    code: u64,
+    k: u8,
+}
+
+impl Kmer {
+
+    /// Maximum length of a k-mer.
+    pub const MAX_LEN: usize = 32;
+
+    /// Packs nucleotides of `s`, every byte must be one of `A`, `C`, `G`
+    /// and `T`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `s` is longer than `MAX_LEN`.
+    pub fn from_slice(s: &[u8]) -> Result<Kmer, AsciiError> {
+        Kmer::check_len(s.len());
+        let mut kmer = Kmer { code: 0, k: s.len() as u8 };
+        for (i, &nuc) in s.iter().enumerate() {
+            match code(nuc) {
+                Some(c) => kmer.code = (kmer.code << 2) | c,
+                None => return Err(AsciiError::new(i, nuc)),
+            }
+        }
+        Ok(kmer)
+    }
+
+    /// K-mer of length `k` with the given code.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is larger than `MAX_LEN` or `code` is not less than
+    /// `4^k`.
+    pub fn from_code(code: u64, k: usize) -> Kmer {
+        Kmer::check_len(k);
+        assert!(code & !Kmer::mask(k) == 0, "code {} is too large for {}-mers", code, k);
+        Kmer { code, k: k as u8 }
+    }
+
+    fn check_len(k: usize) {
+        assert!(k <= Kmer::MAX_LEN, "k-mer length {} is larger than {}", k, Kmer::MAX_LEN);
+    }
+
+    /// Mask of the `2 * k` low bits.
+    fn mask(k: usize) -> u64 {
+        if k == Kmer::MAX_LEN { !0 } else { (1 << (2 * k)) - 1 }
+    }
+
+    /// Lexicographic rank of the k-mer among k-mers of the same length.
+    pub fn code(&self) -> u64 {
+        self.code
+    }
+
+    /// Code as an index of frequency arrays of `4^k` elements.
+    pub fn index(&self) -> usize {
+        self.code as usize
+    }
+
+    pub fn len(&self) -> usize {
+        self.k as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.k == 0
+    }
+
+    /// Nucleotide at position `i`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is out of bounds.
+    pub fn get(&self, i: usize) -> u8 {
+        assert!(i < self.len(), "index {} is out of bounds", i);
+        DnaAlphabet::symbol(((self.code >> (2 * (self.len() - 1 - i))) & 3) as usize)
+    }
+
+    /// Rolling iterator over k-mers of `seq` with their positions, each
+    /// step is O(1).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is 0 or larger than `MAX_LEN`.
+    pub fn iter(seq: &[u8], k: usize) -> Kmers<'_> {
+        Kmers::new(seq, k)
+    }
+
+    /// Unpacks into `Dna`.
+    pub fn to_dna(&self) -> Dna {
+        Dna::new((0..self.len()).map(|i| self.get(i)).collect())
+    }
+
+    /// Next k-mer of a sequence, dropping the first nucleotide and
+    /// appending the one with 2-bit code `c`.
+    #[inline]
+    pub(super) fn roll(self, c: u64) -> Kmer {
+        Kmer { code: ((self.code << 2) | c) & Kmer::mask(self.len()), k: self.k }
+    }
+
+    /// Return reverse complement of the k-mer.
+    pub fn reverse_complement(&self) -> Kmer {
+        if self.k == 0 {
+            return *self;
+        }
+        // complement of a code is `3 - code`, bitwise negation
+        let code = reverse_pairs(!self.code) >> (64 - 2 * self.len());
+        Kmer { code, k: self.k }
+    }
+
+    /// Canonical form, the lexicographically smaller of the k-mer and its
+    /// reverse complement, so that both strands count as the same k-mer.
+    pub fn canonical(&self) -> Kmer {
+        ::std::cmp::min(*self, self.reverse_complement())
+    }
+
+}
+
+impl Ord for Kmer {
+    fn cmp(&self, other: &Kmer) -> Ordering {
+        // compare common prefixes, then lengths
+        let k = ::std::cmp::min(self.len(), other.len());
+        let prefix = |kmer: &Kmer| kmer.code.checked_shr(2 * (kmer.len() - k) as u32).unwrap_or(0);
+        prefix(self).cmp(&prefix(other)).then(self.k.cmp(&other.k))
+    }
+}
+
+impl PartialOrd for Kmer {
+    fn partial_cmp(&self, other: &Kmer) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<&Kmer> for Dna {
+    fn from(kmer: &Kmer) -> Dna {
+        kmer.to_dna()
+    }
+}
+
+impl fmt::Display for Kmer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.to_dna(), f)
+    }
+}
+
+/// Rolling iterator over k-mers of a sequence with their positions, see
+/// `Kmer::iter`. K-mers containing bytes other than `A`, `C`, `G` and `T`
+/// are skipped, masked ones included, see `dust`.
+pub struct Kmers<'a> {
+    seq: &'a [u8],
+    kmer: Kmer,
+    /// Number of valid nucleotides before `next`, up to `k`.
+    valid: usize,
+    /// Position of the next nucleotide to shift in.
+    next: usize,
+}
+
+impl<'a> Kmers<'a> {
+
+    fn new(seq: &'a [u8], k: usize) -> Kmers<'a> {
+        assert!(k > 0, "k-mer length must be positive");
+        Kmer::check_len(k);
+        Kmers { seq, kmer: Kmer { code: 0, k: k as u8 }, valid: 0, next: 0 }
+    }
+
+}
+
+impl<'a> Iterator for Kmers<'a> {
+    type Item = (usize, Kmer);
+
+    fn next(&mut self) -> Option<(usize, Kmer)> {
+        let k = self.kmer.len();
+        while self.next < self.seq.len() {
+            let nuc = self.seq[self.next];
+            self.next += 1;
+            match code(nuc) {
+                Some(c) => {
+                    self.kmer = self.kmer.roll(c);
+                    self.valid += 1;
+                    if self.valid >= k {
+                        return Some((self.next - k, self.kmer));
+                    }
+                }
+                None => self.valid = 0,
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let k = self.kmer.len();
+        let rest = self.seq.len() - self.next;
+        let max = (rest + ::std::cmp::min(self.valid, k - 1)).saturating_sub(k - 1);
+        (0, Some(max))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::collections::HashSet;
+
+    use super::Kmer;
+    use alphabet::DnaAlphabet;
+    use dna::Dna;
+    use strings::pattern_to_number;
+
+    #[test]
+    fn test_code() {
+        for &s in [&b""[..], b"A", b"TTT", b"GATTACA", b"ACGTACGTACGTACGTACGTACGTACGTACGT"].iter() {
+            let kmer = Kmer::from_slice(s).unwrap();
+            assert_eq!(kmer.to_dna(), Dna::from_slice(s));
+            assert_eq!(kmer.index(), pattern_to_number::<DnaAlphabet>(s));
+            assert_eq!(Kmer::from_code(kmer.code(), s.len()), kmer);
+        }
+        let err = Kmer::from_slice(b"ACgT").unwrap_err();
+        assert_eq!((err.valid_up_to(), err.byte()), (2, b'g'));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_too_long() {
+        let _ = Kmer::from_slice(&[b'A'; 33]);
+    }
+
+    #[test]
+    fn test_reverse_complement() {
+        for &s in ["A", "AC", "GATTACA", "ACGTACGTACGTACGTACGTACGTACGTACGG"].iter() {
+            let kmer = Kmer::from_slice(s.as_bytes()).unwrap();
+            let rcomp = Dna::from_str(s).reverse_complement();
+            assert_eq!(kmer.reverse_complement().to_dna(), rcomp);
+            assert_eq!(kmer.canonical(), kmer.reverse_complement().canonical());
+        }
+    }
+
+    #[test]
+    fn test_order() {
+        let mut kmers: Vec<_> = ["T", "GA", "", "GATC", "GAT", "AT"].iter()
+            .map(|s| Kmer::from_slice(s.as_bytes()).unwrap())
+            .collect();
+        kmers.sort();
+        let sorted: Vec<_> = kmers.iter().map(|kmer| kmer.to_string()).collect();
+        assert_eq!(sorted, ["", "AT", "GA", "GAT", "GATC", "T"]);
+    }
+
+    #[test]
+    fn test_iter() {
+        let seq = b"ACGTTGCANNAGTCCA";
+        for k in 1..6 {
+            let kmers: Vec<_> = Kmer::iter(seq, k).collect();
+            let expected: Vec<_> = seq.windows(k)
+                .enumerate()
+                .filter_map(|(i, w)| Kmer::from_slice(w).ok().map(|kmer| (i, kmer)))
+                .collect();
+            assert_eq!(kmers, expected);
+        }
+        assert_eq!(Kmer::iter(b"ACG", 4).count(), 0);
+
+        let canonical: HashSet<_> = Kmer::iter(b"AACGTT", 2).map(|(_, kmer)| kmer.canonical()).collect();
+        assert_eq!(canonical.len(), 3);
+    }
+
+}
@@ -1,15 +1,26 @@
 //! Algorithms on DNA sequences
 
 mod dna_impl;
+mod kmer;
+mod packed;
+pub mod composition;
+pub mod dust;
+pub mod iupac;
 
 use std::collections::HashSet;
 use std::f64;
+use std::marker::PhantomData;
+use std::str;
 
 use rand;
 use rand::distributions::{IndependentSample, Range, WeightedChoice, Weighted};
 
-pub use self::dna_impl::Dna;
+pub use self::dna_impl::{AsciiError, AsciiErrorKind, Dna};
+pub use self::kmer::{Kmer, Kmers};
+pub use self::packed::{PackedDna, PackedKmers};
+use ::alphabet::{Alphabet, DnaAlphabet};
 use ::seq;
+use ::strings;
 
 type Seq = Vec<u8>;
 
@@ -18,50 +29,34 @@ pub const T: u8 = b'T';
 pub const G: u8 = b'G';
 pub const C: u8 = b'C';
 
-pub static ALPHABET: [u8; 4] = [A, T, G, C];
+/// Nucleotides in the order of their ranks, an alias of
+/// `DnaAlphabet::SYMBOLS`.
+pub const ALPHABET: &[u8] = DnaAlphabet::SYMBOLS;
 
-/// Returns all permutations of `text` within Hamming distance of `d`.
+/// Returns all permutations of `text` within Hamming distance of `d`, see
+/// `strings::neighbors`.
 pub fn neighbors(text: &[u8], d: usize) -> Vec<Vec<u8>> {
-    let mut res = HashSet::new();
-    if d == 0 {
-        res.insert(text.to_vec());
-        res.into_iter().collect()
-    } else if text.len() == 1 {
-        for a in ALPHABET.iter().cloned() {
-            res.insert(vec![a]);
-        }
-        res.into_iter().collect()
-    } else {
-        let tail = &text[1..];
-        let suffixes = neighbors(tail, d);
-        for suffix in suffixes.iter() {
-            if seq::hamming_distance(tail, suffix) < d {
-                for a in ALPHABET.iter().cloned() {
-                    res.insert(seq::concat(&[a], suffix));
-                }
-            } else {
-                let h = &text[0..1];
-                res.insert(seq::concat(h, suffix));
-            }
-        }
-        res.into_iter().collect()
-    }
+    let text = str::from_utf8(text).expect("neighbors: DNA should be ascii");
+    strings::neighbors::<DnaAlphabet>(text, d)
+        .into_iter()
+        .map(String::into_bytes)
+        .collect()
 }
 
-/// Returns distance between `pattern` and DNA strings `dnas`
+/// Returns distance between `pattern` and DNA strings `dnas`. Strings
+/// without an unmasked k-mer, see `dust`, are skipped.
 fn distance(dnas: &[Dna], pattern: &Dna) -> usize {
     let k = pattern.len();
     let mut distance = 0;
 
     for dna in dnas.iter() {
-        let mut h = usize::max_value();
-        for kmer in dna.windows(k) {
-            let d = seq::hamming_distance(pattern, kmer);
-            if h > d {
-                h = d;
-            }
+        let h = dna.windows(k)
+            .filter(|kmer| !dust::has_masked(kmer))
+            .map(|kmer| seq::hamming_distance(pattern, kmer))
+            .min();
+        if let Some(h) = h {
+            distance += h;
         }
-        distance += h;
     }
 
     distance
@@ -73,11 +68,12 @@ fn distance(dnas: &[Dna], pattern: &Dna) -> usize {
 
 /// Given a collection of strings Dna and an integer d, a k-mer is a
 /// (k,d)-motif if it appears in every string from Dna with at most d
-/// mismatches.
+/// mismatches. Masked k-mers of the first string, see `dust`, are not
+/// considered.
 pub fn motif_enumeration(dnas: &[Dna], k: usize, d: usize) -> HashSet<Dna> {
     let mut motifs = HashSet::new();
     let ref dna0 = dnas[0];
-    for kmer in dna0.windows(k) {
+    for kmer in dna0.windows(k).filter(|kmer| !dust::has_masked(kmer)) {
         for kdmer in neighbors(kmer, d).iter() {
             let all_contains = dnas.iter().all(|dna| {
                 let (inds, _) = seq::find_by(dna, kdmer, |a, b| seq::hamming_distance(a, b) <= d);
@@ -92,11 +88,13 @@ pub fn motif_enumeration(dnas: &[Dna], k: usize, d: usize) -> HashSet<Dna> {
 }
 
 /// Returns median of length `k` for the vector of DNA strings `dnas`.
+/// Among equally distant k-mers the first one in `ALPHABET` order, `A`,
+/// `C`, `G`, `T`, is returned.
 pub fn median_string(dnas: &[Dna], k: usize) -> Dna {
     let mut d = usize::max_value();
     let mut median = Dna::new(vec![]);
-    for kmer in seq::permutations_with_repetitions(&ALPHABET, k) {
-        let pattern = Dna::new(kmer);
+    for code in 0..4u64.pow(k as u32) {
+        let pattern = Kmer::from_code(code, k).to_dna();
         let dk_distance = distance(dnas, &pattern);
         if d > dk_distance {
             d = dk_distance;
@@ -106,37 +104,86 @@ pub fn median_string(dnas: &[Dna], k: usize) -> Dna {
     median
 }
 
-/// DNA Profile for motif matrix for which `P_i,j` is the frequency of
-/// the i-th nucleotide in the j-th column of the motif matrix.
+/// Profile for motif matrix for which `P_i,j` is the frequency of the
+/// i-th symbol of alphabet `A` in the j-th column of the motif matrix.
 ///
 /// Note that the elements of any column of the profile matrix sum to 1.
-pub struct Profile {
-    a: Vec<f64>,
-    c: Vec<f64>,
-    g: Vec<f64>,
-    t: Vec<f64>,
+///
+/// # Examples
+///
+/// Profiles work on any alphabet, such as protein motifs:
+///
+/// ```
+/// use bio::alphabet::ProteinAlphabet;
+/// use bio::dna::Profile;
+///
+/// let p = Profile::<ProteinAlphabet>::from_motifs(&[b"MKV", b"MRV"]);
+/// assert_eq!(p.value(b'K', 1), 0.5);
+/// assert_eq!(p.probability(b"MKV"), 0.5);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Profile<A = DnaAlphabet> {
+    /// Row per symbol, in the order of symbol ranks.
+    rows: Vec<Vec<f64>>,
+    alphabet: PhantomData<A>,
 }
 
 impl Profile {
 
     /// Creates new `Profile` matrix from given rows.
     pub fn new(pa: Vec<f64>, pc: Vec<f64>, pg: Vec<f64>, pt: Vec<f64>) -> Profile {
-        assert_eq!(pa.len(), pc.len());
-        assert_eq!(pa.len(), pg.len());
-        assert_eq!(pa.len(), pt.len());
-        Profile { a: pa, c: pc, g: pg, t: pt }
+        Profile::from_rows(vec![pa, pc, pg, pt])
+    }
+
+    /// Updates `p` to an average of `l`. Used as an update function for
+    /// `vector`.
+    #[inline]
+    pub fn avg_mean(p: &mut f64, l: f64) { *p /= l }
+
+    /// Updates `p` to a normalized average using Laplace's Rule of
+    /// Succession algorithm. Used as an update funiction for
+    /// `vector`
+    #[inline]
+    pub fn avg_laplace(p: &mut f64, l: f64) { *p = (*p + 1.) / (2. * l) }
+
+}
+
+impl<A: Alphabet> Profile<A> {
+
+    /// Creates new `Profile` matrix from rows of every symbol, in the order
+    /// of symbol ranks.
+    pub fn from_rows(rows: Vec<Vec<f64>>) -> Profile<A> {
+        assert_eq!(rows.len(), A::size());
+        assert!(rows.iter().all(|row| row.len() == rows[0].len()));
+        Profile { rows, alphabet: PhantomData }
+    }
+
+    /// Creates new `Profile` from symbol counts, `counts[i]` holds the
+    /// counts of every symbol in the `i`-th column, such as `A`, `C`, `G`
+    /// and `T`. Each column is normalized to sum to 1.
+    pub fn from_counts<C: AsRef<[f64]>>(counts: &[C]) -> Profile<A> {
+        let mut rows = vec![Vec::with_capacity(counts.len()); A::size()];
+        for col in counts.iter() {
+            let col = col.as_ref();
+            assert_eq!(col.len(), A::size());
+            let sum: f64 = col.iter().sum();
+            for (row, &n) in rows.iter_mut().zip(col.iter()) {
+                row.push(if sum > 0. { n / sum } else { 1. / A::size() as f64 });
+            }
+        }
+        Profile::from_rows(rows)
     }
 
-    /// Return profile vector for nucleotide `n`. Where `profile[i]` is a frequency
-    /// of nucleotide `n` in the `i`-th column of matrix `motifs`.
-    fn vector<F>(n: u8, motifs: &[Dna], avg: &F) -> Vec<f64>
-        where F: Fn(&mut f64, f64) {
+    /// Return profile vector for symbol `n`. Where `profile[i]` is a frequency
+    /// of symbol `n` in the `i`-th column of matrix `motifs`.
+    fn vector<F, S>(n: u8, motifs: &[S], avg: &F) -> Vec<f64>
+        where F: Fn(&mut f64, f64), S: AsRef<[u8]> {
 
         let len = motifs.len() as f64;
-        let mut profile = vec![0.; motifs[0].len()];
+        let mut profile = vec![0.; motifs[0].as_ref().len()];
 
         for motif in motifs.iter() {
-            for (p, m) in profile.iter_mut().zip(motif.iter()) {
+            for (p, m) in profile.iter_mut().zip(motif.as_ref().iter()) {
                 if *m == n {
                     *p += 1.;
                 }
@@ -152,91 +199,114 @@ impl Profile {
 
     /// Build a `Profile` for `motifs` matrix using default `avg_mean`
     /// average algorithm.
-    pub fn from_motifs(motifs: &[Dna]) -> Profile {
+    pub fn from_motifs<S: AsRef<[u8]>>(motifs: &[S]) -> Profile<A> {
         Profile::build(motifs, &Profile::avg_mean)
     }
 
     /// Build a `Profile` for `motifs` matrix using `avg` average
     /// algorithm.
-    pub fn build<F>(dnas: &[Dna], avg: &F) -> Profile
-        where F: Fn(&mut f64, f64) {
-
-        let pa = Profile::vector(A, &dnas, avg);
-        let pc = Profile::vector(C, &dnas, avg);
-        let pg = Profile::vector(G, &dnas, avg);
-        let pt = Profile::vector(T, &dnas, avg);
+    pub fn build<F, S>(motifs: &[S], avg: &F) -> Profile<A>
+        where F: Fn(&mut f64, f64), S: AsRef<[u8]> {
 
-        Profile::new(pa, pc, pg, pt)
+        let rows = A::SYMBOLS.iter()
+            .map(|&n| Profile::<A>::vector(n, motifs, avg))
+            .collect();
+        Profile::from_rows(rows)
     }
 
     /// Length (width) of `Prifile` matrix
-    fn len(&self) -> usize {
-        self.a.len()
+    pub fn len(&self) -> usize {
+        self.rows[0].len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
     }
 
-    /// Profile value for `nuc` at position `i`.
-    fn value(&self, nuc: u8, i: usize) -> f64 {
-        match nuc {
-            A => self.a[i],
-            C => self.c[i],
-            G => self.g[i],
-            T => self.t[i],
-            _ => panic!("Unsupported character {}", nuc as char),
+    /// Profile value for `sym` at position `i`.
+    pub fn value(&self, sym: u8, i: usize) -> f64 {
+        match A::rank(sym) {
+            Some(rank) => self.rows[rank][i],
+            None => panic!("Unsupported character {}", sym as char),
         }
     }
 
-    /// `i`-th column with labels
-    fn column(&self, i: usize) -> [(u8, f64); 4] {
-        [(A, self.a[i]), (C, self.c[i]), (G, self.g[i]), (T, self.t[i])]
+    /// `i`-th column with labels, in the order of symbol ranks, `A`, `C`,
+    /// `G`, `T` for DNA.
+    pub fn column(&self, i: usize) -> Vec<(u8, f64)> {
+        A::SYMBOLS.iter().cloned()
+            .zip(self.rows.iter().map(|row| row[i]))
+            .collect()
     }
 
-    /// Most popular nucleotide in `i`-th column.
+    /// Most popular symbol in `i`-th column.
     fn most_popular(&self, i: usize) -> u8 {
         let mut col = self.column(i);
         col.sort_by(|&(_, fa), &(_, fb)| fb.partial_cmp(&fa).unwrap());
-        let (nuc, _) = col[0];
-        nuc
+        let (sym, _) = col[0];
+        sym
     }
 
-    /// Updates `p` to an average of `l`. Used as an update function for
-    /// `vector`.
-    #[inline]
-    pub fn avg_mean(p: &mut f64, l: f64) { *p = *p / l }
-
-    /// Updates `p` to a normalized average using Laplace's Rule of
-    /// Succession algorithm. Used as an update funiction for
-    /// `vector`
-    #[inline]
-    pub fn avg_laplace(p: &mut f64, l: f64) { *p = (*p + 1.) / (2. * l) }
+    /// Probability of occurrence of `seq` given the profile.
+    pub fn probability(&self, seq: &[u8]) -> f64 {
+        seq.iter()
+            .enumerate()
+            .fold(1., |acc, (i, &c)| acc * self.value(c, i))
+    }
 
 }
 
 /// Compute probabilities of k-mers based on given probability distribution.
-pub fn kmer_probabilities<'a>(dna: &'a Dna, k: usize, p: &'a Profile) -> Box<Iterator<Item = (f64, Dna)> + 'a> {
+/// Masked k-mers, see `dust`, are skipped, so motif searches ignore
+/// low-complexity regions masked beforehand.
+pub fn kmer_probabilities<'a>(dna: &'a [u8], k: usize, p: &'a Profile) -> Box<Iterator<Item = (f64, Dna)> + 'a> {
     let it = dna.windows(k)
+        .filter(|kmer| !dust::has_masked(kmer))
         .map(move |kmer| (probability(kmer, p), Dna::from_slice(kmer)));
     Box::new(it)
 }
 
-/// Search for a kmer with highest probability given `p` Profile.
-pub fn most_probable_kmer(dna: &Dna, k: usize, p: &Profile) -> (f64, Dna) {
+/// Search for a kmer with highest probability given `p` Profile. Returns
+/// `None` if `dna` has no unmasked k-mer, see `dust`.
+///
+/// # Examples
+///
+/// ```
+/// use bio::dna::{Profile, most_probable_kmer};
+///
+/// let p: Profile = Profile::from_motifs(&[b"ACG", b"ACT"]);
+/// let (_, kmer) = most_probable_kmer(b"TTACTG", 3, &p).unwrap();
+/// assert_eq!(kmer.as_str(), "ACT");
+/// assert!(most_probable_kmer(b"ttactg", 3, &p).is_none());
+/// ```
+pub fn most_probable_kmer(dna: &[u8], k: usize, p: &Profile) -> Option<(f64, Dna)> {
     kmer_probabilities(dna, k, p)
-        .fold((f64::MIN, Dna::new(vec![])), |(acc, d), (score, dna)| if score > acc { (score, dna) } else { (acc, d) } )
+        .fold(None, |best, (score, dna)| match best {
+            Some((acc, _)) if acc >= score => best,
+            _ => Some((score, dna)),
+        })
 }
 
 /// Greedy algorithm for motif finding.
+///
+/// Strings without an unmasked k-mer, see `dust`, are skipped in all the
+/// motif searches: their motif stays a masked k-mer, which counts in no
+/// score and has no position in `motif_positions`.
 pub fn greedy_motif_search(dnas: &[Dna], k: usize, with_pseudocounts: bool) -> Vec<Dna> {
     let update = if with_pseudocounts { Profile::avg_laplace } else { Profile::avg_mean };
     let mut best_motifs: Vec<_> = dnas.iter()
         .map(|dna| Dna::from_slice(&dna[0..k]))
         .collect();
 
-    for kmer in dnas[0].windows(k) {
+    for kmer in dnas[0].windows(k).filter(|kmer| !dust::has_masked(kmer)) {
         let mut motifs = Vec::with_capacity(dnas.len());
         motifs.push(Dna::from_slice(kmer));
-        for dna in dnas[1..].iter() {
+        for (dna, first) in dnas[1..].iter().zip(best_motifs[1..].iter()) {
             let p = Profile::build(&motifs, &update);
-            let (_, most_probable) = most_probable_kmer(&dna, k, &p);
+            let most_probable = match most_probable_kmer(dna, k, &p) {
+                Some((_, kmer)) => kmer,
+                None => first.clone(),
+            };
             motifs.push(most_probable);
         }
         if score(&motifs) < score(&best_motifs) {
@@ -259,11 +329,12 @@ fn randomized_motif_search_iteration(dnas: &[Dna], k: usize) -> Vec<Dna> {
     loop {
         let p = Profile::build(&motifs, &Profile::avg_laplace);
         let ms: Vec<_> = dnas.iter()
-            .fold(Vec::with_capacity(dnas.len()), |mut acc, dna| {
-                let (_, most_probable) = most_probable_kmer(&dna, k, &p);
-                acc.push(most_probable);
-                acc
-            });
+            .zip(motifs.iter())
+            .map(|(dna, motif)| match most_probable_kmer(dna, k, &p) {
+                Some((_, kmer)) => kmer,
+                None => motif.clone(),
+            })
+            .collect();
         motifs = ms;
         if score(&motifs) < score(&best_motifs) {
             best_motifs = motifs.clone();
@@ -305,9 +376,9 @@ pub fn gibbs_sampler_iteration(dnas: &[Dna], k: usize, t: usize, n: usize) -> Ve
 
     for _ in 0..n {
         let i = motif_range.ind_sample(&mut rng);
-        motifs.remove(i);
+        let old = motifs.remove(i);
         let p = Profile::build(&motifs, &Profile::avg_laplace);
-        let motif = randomly_generated(&dnas[i], k, &p, &mut rng);
+        let motif = randomly_generated(&dnas[i], k, &p, &mut rng).unwrap_or(old);
         motifs.insert(i, motif);
 
         if score(&motifs) < score(&best_motifs) {
@@ -335,6 +406,27 @@ pub fn gibbs_sampler(dnas: &[Dna], k: usize, t: usize, n: usize, iters: usize) -
     best_motifs
 }
 
+/// Positions of `motifs`, as found by the motif searches, in DNA strings
+/// `dnas`: the first occurrence of `motifs[i]` in `dnas[i]`, `None` if it
+/// does not occur or is masked, see `dust`. See `Interval::from_motifs` to
+/// write them as a track.
+///
+/// # Examples
+///
+/// ```
+/// use bio::dna::{self, Dna};
+///
+/// let dnas = [Dna::from_str("GGCGTTCAGGCA"), Dna::from_str("AAGAATCAGTCA")];
+/// let motifs = dna::greedy_motif_search(&dnas, 3, false);
+/// assert_eq!(dna::motif_positions(&dnas, &motifs), [Some(5), Some(5)]);
+/// ```
+pub fn motif_positions(dnas: &[Dna], motifs: &[Dna]) -> Vec<Option<usize>> {
+    dnas.iter()
+        .zip(motifs.iter())
+        .map(|(dna, motif)| if dust::has_masked(motif) { None } else { seq::find_iter(dna, motif).next() })
+        .collect()
+}
+
 /// Return score, as a cumulative Hamming distance between `consensus`
 /// string for `motifs` matrix and `motifs` matrix itself.
 fn score(motifs: &[Dna]) -> usize {
@@ -344,19 +436,22 @@ fn score(motifs: &[Dna]) -> usize {
 /// Return probability of occurrence of the `dna` sequence given `p`
 /// probability distribution.
 fn probability(dna: &[u8], p: &Profile) -> f64 {
-    dna.iter()
-        .enumerate()
-        .fold(1., |acc, (i, &c)| acc * p.value(c, i))
+    p.probability(dna)
 }
 
-/// Returns weighted random kmer of `dna` based on `p` probability distribution.
-fn randomly_generated<R: rand::Rng>(dna: &[u8], k: usize, p: &Profile, rng: &mut R) -> Dna {
+/// Returns weighted random kmer of `dna` based on `p` probability
+/// distribution, `None` if `dna` has no unmasked k-mer.
+fn randomly_generated<R: rand::Rng>(dna: &[u8], k: usize, p: &Profile, rng: &mut R) -> Option<Dna> {
     let ceil = u16::max_value() as u32;
     // bigger probability distribution gives better result
     //let ceil = u32::max_value() / 100;
     let probabilities: Vec<_> = dna.windows(k)
+        .filter(|kmer| !dust::has_masked(kmer))
         .map(|kmer| (probability(kmer, &p), kmer))
         .collect();
+    if probabilities.is_empty() {
+        return None;
+    }
     let (min, max) = probabilities.iter()
         .fold((f64::MAX, f64::MIN), |acc, &(pr, _)| (acc.0.min(pr), acc.1.max(pr)));
     // maps f64 probabilities to the u32 in a range 1...ceil
@@ -368,7 +463,7 @@ fn randomly_generated<R: rand::Rng>(dna: &[u8], k: usize, p: &Profile, rng: &mut
         }).collect();
 
     let wc = WeightedChoice::new(&mut weights);
-    Dna::from_slice(wc.ind_sample(rng))
+    Some(Dna::from_slice(wc.ind_sample(rng)))
 }
 
 /// Return coefficients to scale interval [min, max] into interval [a, b].
@@ -407,7 +502,7 @@ pub fn scale_coeffs(min: f64, max: f64, a: u32, b: u32) -> (f64, f64) {
 /// the `motifs` matrix.
 fn consensus(motifs: &[Dna]) -> Dna {
     let mut vec = Vec::new();
-    let p = Profile::from_motifs(motifs);
+    let p: Profile = Profile::from_motifs(motifs);
 
     for i in 0..p.len() {
         vec.push(p.most_popular(i));
@@ -419,7 +514,9 @@ fn consensus(motifs: &[Dna]) -> Dna {
 #[cfg(test)]
 mod tests {
 
-    use super::Dna;
+    use rand;
+
+    use super::{Dna, Profile};
 
     #[test]
     fn distance() {
@@ -431,4 +528,33 @@ mod tests {
         assert_eq!(super::distance(&dnas, &pattern), 5);
     }
 
+    #[test]
+    fn fully_masked() {
+        let dnas = [Dna::from_str("ACGTAC"), Dna::from_str("acgtac"), Dna::from_str("ACGTTT")];
+        assert_eq!(super::distance(&dnas, &Dna::from_str("ACG")), 0);
+        assert_eq!(super::distance(&dnas, &Dna::from_str("TTA")), 2);
+
+        let motifs = super::greedy_motif_search(&dnas, 3, false);
+        assert_eq!(motifs.len(), 3);
+        assert_eq!(super::motif_positions(&dnas, &motifs)[1], None);
+        assert_eq!(super::randomized_motif_search(&dnas, 3, 5).len(), 3);
+        assert_eq!(super::gibbs_sampler(&dnas, 3, 3, 20, 5).len(), 3);
+
+        let p: Profile = Profile::from_motifs(&[b"ACG"]);
+        let mut rng = rand::thread_rng();
+        assert!(super::most_probable_kmer(b"acgtac", 3, &p).is_none());
+        assert!(super::randomly_generated(b"acgNNN", 3, &p, &mut rng).is_none());
+    }
+
+    #[test]
+    fn ties_in_alphabet_order() {
+        // C and T are equally good, ranks order DNA as A, C, G, T
+        let dnas = [Dna::from_str("CT"), Dna::from_str("TC")];
+        assert_eq!(super::median_string(&dnas, 1).as_str(), "C");
+        let p: Profile = Profile::from_motifs(&dnas);
+        let symbols: Vec<_> = p.column(0).into_iter().map(|(sym, _)| sym).collect();
+        assert_eq!(symbols, b"ACGT");
+        assert_eq!(p.most_popular(0), b'C');
+    }
+
 }
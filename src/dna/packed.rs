@@ -0,0 +1,243 @@
+//! DNA implementation packing 2 bits per nucleotide into u64 words.
+
+use std::fmt;
+
+use ::alphabet::{Alphabet, DnaAlphabet};
+use super::dna_impl::{AsciiError, Dna};
+use super::kmer::Kmer;
+
+const BASES_PER_WORD: usize = 32;
+
+/// 2-bit code of `nuc`, its `DnaAlphabet` rank, the same order as
+/// `strings::pattern_to_number` uses.
+pub(super) fn code(nuc: u8) -> Option<u64> {
+    DnaAlphabet::rank(nuc).map(|r| r as u64)
+}
+
+/// Reverses order of the 2-bit groups of `x`.
+pub(super) fn reverse_pairs(x: u64) -> u64 {
+    let x = x.swap_bytes();
+    let x = ((x >> 4) & 0x0F0F_0F0F_0F0F_0F0F) | ((x & 0x0F0F_0F0F_0F0F_0F0F) << 4);
+    ((x >> 2) & 0x3333_3333_3333_3333) | ((x & 0x3333_3333_3333_3333) << 2)
+}
+
+/// DNA string using 2 bits per nucleotide, a quarter of the memory of
+/// `Dna`. Only `A`, `C`, `G` and `T` can be stored.
+///
+/// # Examples
+///
+/// ```
+/// use bio::dna::{Dna, Kmer, PackedDna};
+///
+/// let packed = PackedDna::from_slice(b"AATGC").unwrap();
+/// assert_eq!(packed.len(), 5);
+/// assert_eq!(packed.get(2), b'T');
+/// assert_eq!(packed.reverse_complement().to_dna(), Dna::from_str("GCATT"));
+///
+/// let kmers: Vec<_> = packed.kmers(4).collect();
+/// assert_eq!(kmers, [Kmer::from_slice(b"AATG").unwrap(), Kmer::from_slice(b"ATGC").unwrap()]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PackedDna {
+    /// Nucleotide `i` is stored in the word `i / 32`, starting from the
+    /// most significant bits. Unused bits of the last word are zero.
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl PackedDna {
+
+    /// Packs nucleotides of `s`, every byte must be one of `A`, `C`, `G`
+    /// and `T`.
+    pub fn from_slice(s: &[u8]) -> Result<PackedDna, AsciiError> {
+        let mut words = vec![0; s.len().div_ceil(BASES_PER_WORD)];
+        for (i, &nuc) in s.iter().enumerate() {
+            match code(nuc) {
+                Some(c) => words[i / BASES_PER_WORD] |= c << PackedDna::shift(i),
+                None => return Err(AsciiError::new(i, nuc)),
+            }
+        }
+        Ok(PackedDna { words, len: s.len() })
+    }
+
+    /// Unpacks into `Dna`.
+    pub fn to_dna(&self) -> Dna {
+        Dna::new(self.iter().collect())
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Bit offset of the nucleotide `i` in its word.
+    #[inline]
+    fn shift(i: usize) -> usize {
+        62 - 2 * (i % BASES_PER_WORD)
+    }
+
+    /// 2-bit code of the nucleotide at position `i`.
+    #[inline]
+    fn code(&self, i: usize) -> u64 {
+        (self.words[i / BASES_PER_WORD] >> PackedDna::shift(i)) & 3
+    }
+
+    /// Nucleotide at position `i`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is out of bounds.
+    pub fn get(&self, i: usize) -> u8 {
+        assert!(i < self.len, "index {} is out of bounds", i);
+        DnaAlphabet::symbol(self.code(i) as usize)
+    }
+
+    /// Iterator over nucleotides.
+    pub fn iter(&self) -> Box<dyn Iterator<Item = u8> + '_> {
+        Box::new((0..self.len).map(move |i| DnaAlphabet::symbol(self.code(i) as usize)))
+    }
+
+    /// Iterator over all k-mers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is 0 or larger than `Kmer::MAX_LEN`.
+    pub fn kmers(&self, k: usize) -> PackedKmers<'_> {
+        PackedKmers::new(self, k)
+    }
+
+    /// Return reverse complement of the DNA string, computed on whole
+    /// words.
+    pub fn reverse_complement(&self) -> PackedDna {
+        // complement of a code is `3 - code`, bitwise negation
+        let mut words: Vec<_> = self.words.iter()
+            .rev()
+            .map(|&w| reverse_pairs(!w))
+            .collect();
+        let pad = words.len() * BASES_PER_WORD - self.len;
+        if pad > 0 {
+            // drop complemented padding moved to the front
+            let s = 2 * pad;
+            for i in 0..words.len() {
+                let next = words.get(i + 1).map_or(0, |&w| w >> (64 - s));
+                words[i] = (words[i] << s) | next;
+            }
+        }
+        PackedDna { words, len: self.len }
+    }
+
+}
+
+impl<'a> From<&'a PackedDna> for Dna {
+    fn from(packed: &'a PackedDna) -> Dna {
+        packed.to_dna()
+    }
+}
+
+impl fmt::Display for PackedDna {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.to_dna(), f)
+    }
+}
+
+/// Iterator over k-mers of `PackedDna`, see `PackedDna::kmers`.
+pub struct PackedKmers<'a> {
+    dna: &'a PackedDna,
+    kmer: Kmer,
+    /// Position of the next nucleotide to shift in.
+    next: usize,
+}
+
+impl<'a> PackedKmers<'a> {
+
+    fn new(dna: &'a PackedDna, k: usize) -> PackedKmers<'a> {
+        assert!(k > 0, "k-mer length must be positive");
+        let mut kmer = Kmer::from_code(0, k);
+        let next = if dna.len() < k {
+            dna.len()
+        } else {
+            for i in 0..k - 1 {
+                kmer = kmer.roll(dna.code(i));
+            }
+            k - 1
+        };
+        PackedKmers { dna, kmer, next }
+    }
+
+}
+
+impl<'a> Iterator for PackedKmers<'a> {
+    type Item = Kmer;
+
+    fn next(&mut self) -> Option<Kmer> {
+        if self.next >= self.dna.len() {
+            return None;
+        }
+        self.kmer = self.kmer.roll(self.dna.code(self.next));
+        self.next += 1;
+        Some(self.kmer)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.dna.len() - self.next;
+        (len, Some(len))
+    }
+}
+
+impl<'a> ExactSizeIterator for PackedKmers<'a> {}
+
+#[cfg(test)]
+mod tests {
+
+    use super::PackedDna;
+    use alphabet::DnaAlphabet;
+    use dna::Dna;
+    use strings::pattern_to_number;
+
+    fn sample(len: usize) -> Vec<u8> {
+        (0..len).map(|i| b"ACGTTGCAAGTC"[(i * 7 + i / 5) % 12]).collect()
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        for len in 0..100 {
+            let seq = sample(len);
+            let packed = PackedDna::from_slice(&seq).unwrap();
+            assert_eq!(packed.len(), len);
+            assert_eq!(packed.to_dna(), Dna::new(seq));
+        }
+    }
+
+    #[test]
+    fn test_invalid_nucleotide() {
+        let err = PackedDna::from_slice(b"ACGTN").unwrap_err();
+        assert_eq!((err.valid_up_to(), err.byte()), (4, b'N'));
+    }
+
+    #[test]
+    fn test_reverse_complement() {
+        for len in 0..100 {
+            let dna = Dna::new(sample(len));
+            let packed = PackedDna::from_slice(&dna).unwrap();
+            let rcomp = packed.reverse_complement();
+            assert_eq!(rcomp.to_dna(), dna.reverse_complement());
+            assert_eq!(rcomp, PackedDna::from_slice(&dna.reverse_complement()).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_kmers() {
+        let seq = sample(80);
+        let packed = PackedDna::from_slice(&seq).unwrap();
+        for &k in [1, 3, 31, 32].iter() {
+            let codes: Vec<_> = packed.kmers(k).map(|kmer| kmer.index()).collect();
+            let expected: Vec<_> = seq.windows(k).map(pattern_to_number::<DnaAlphabet>).collect();
+            assert_eq!(codes, expected);
+        }
+        assert_eq!(PackedDna::from_slice(b"ACG").unwrap().kmers(4).count(), 0);
+    }
+
+}
@@ -2,15 +2,19 @@
 
 #![feature(test)]
 
+extern crate flate2;
+extern crate memmap2;
 extern crate rand;
 extern crate test;
 
+pub mod alphabet;
 pub mod dna;
+pub mod peptide;
+pub mod rna;
 pub mod seq;
 pub mod strings;
 
 pub mod data;
 pub mod io;
 
-// experimental
 pub mod adt;
@@ -0,0 +1,238 @@
+//! Peptides, amino acid sequences, and their masses.
+
+use std::error;
+use std::fmt;
+use std::ops;
+use std::str;
+
+/// Monoisotopic mass of water, to be added to the residue masses for the
+/// mass of a neutral peptide.
+pub const WATER_MONOISOTOPIC_MASS: f64 = 18.01056;
+
+/// Integer mass of the amino acid residue `aa`, in daltons.
+///
+/// # Examples
+///
+/// ```
+/// use bio::peptide;
+///
+/// assert_eq!(peptide::integer_mass(b'G'), Some(57));
+/// assert_eq!(peptide::integer_mass(b'I'), peptide::integer_mass(b'L'));
+/// assert_eq!(peptide::integer_mass(b'*'), None);
+/// ```
+pub fn integer_mass(aa: u8) -> Option<u32> {
+    let mass = match aa {
+        b'G' => 57,
+        b'A' => 71,
+        b'S' => 87,
+        b'P' => 97,
+        b'V' => 99,
+        b'T' => 101,
+        b'C' => 103,
+        b'I' | b'L' => 113,
+        b'N' => 114,
+        b'D' => 115,
+        b'K' | b'Q' => 128,
+        b'E' => 129,
+        b'M' => 131,
+        b'H' => 137,
+        b'F' => 147,
+        b'R' => 156,
+        b'Y' => 163,
+        b'W' => 186,
+        _ => return None,
+    };
+    Some(mass)
+}
+
+/// Monoisotopic mass of the amino acid residue `aa`, in daltons.
+pub fn monoisotopic_mass(aa: u8) -> Option<f64> {
+    let mass = match aa {
+        b'G' => 57.02146,
+        b'A' => 71.03711,
+        b'S' => 87.03203,
+        b'P' => 97.05276,
+        b'V' => 99.06841,
+        b'T' => 101.04768,
+        b'C' => 103.00919,
+        b'I' | b'L' => 113.08406,
+        b'N' => 114.04293,
+        b'D' => 115.02694,
+        b'Q' => 128.05858,
+        b'K' => 128.09496,
+        b'E' => 129.04259,
+        b'M' => 131.04049,
+        b'H' => 137.05891,
+        b'F' => 147.06841,
+        b'R' => 156.10111,
+        b'Y' => 163.06333,
+        b'W' => 186.07931,
+        _ => return None,
+    };
+    Some(mass)
+}
+
+/// Amino acid sequence over the 20 standard one-letter codes of
+/// `ProteinAlphabet`.
+///
+/// # Examples
+///
+/// ```
+/// use bio::peptide::Peptide;
+///
+/// let tyrocidine = "VKLFPWFNQY".parse::<Peptide>().unwrap();
+/// assert_eq!(tyrocidine.mass(), 1322);
+/// assert_eq!(&tyrocidine.prefix_masses()[..4], [0, 99, 227, 340]);
+/// assert!((tyrocidine.monoisotopic_mass() - 1322.68).abs() < 0.01);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Peptide {
+    vec: Vec<u8>,
+}
+
+impl Peptide {
+
+    /// Creates `Peptide` from one-letter codes, checking that every byte
+    /// is one of the 20 standard amino acids.
+    pub fn parse_from_slice(s: &[u8]) -> Result<Peptide, AminoAcidError> {
+        match s.iter().position(|&aa| integer_mass(aa).is_none()) {
+            Some(i) => Err(AminoAcidError { valid_up_to: i, byte: s[i] }),
+            None => Ok(Peptide { vec: s.to_vec() }),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        str::from_utf8(&self.vec).expect("peptide should be ASCII")
+    }
+
+    /// Sum of the integer masses of the residues.
+    pub fn mass(&self) -> u32 {
+        self.vec.iter().map(|&aa| integer_mass(aa).unwrap()).sum()
+    }
+
+    /// Sum of the monoisotopic masses of the residues, add
+    /// `WATER_MONOISOTOPIC_MASS` for the mass of the neutral peptide.
+    pub fn monoisotopic_mass(&self) -> f64 {
+        self.vec.iter().map(|&aa| monoisotopic_mass(aa).unwrap()).sum()
+    }
+
+    /// Integer masses of all prefixes, from the empty one to the whole
+    /// peptide.
+    pub fn prefix_masses(&self) -> Vec<u32> {
+        let mut masses = Vec::with_capacity(self.vec.len() + 1);
+        masses.push(0);
+        for &aa in self.vec.iter() {
+            let last = masses[masses.len() - 1];
+            masses.push(last + integer_mass(aa).unwrap());
+        }
+        masses
+    }
+
+    /// Monoisotopic masses of all prefixes, from the empty one to the
+    /// whole peptide.
+    pub fn monoisotopic_prefix_masses(&self) -> Vec<f64> {
+        let mut masses = Vec::with_capacity(self.vec.len() + 1);
+        masses.push(0.);
+        for &aa in self.vec.iter() {
+            let last = masses[masses.len() - 1];
+            masses.push(last + monoisotopic_mass(aa).unwrap());
+        }
+        masses
+    }
+
+}
+
+impl AsRef<[u8]> for Peptide {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl ops::Deref for Peptide {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.vec
+    }
+}
+
+impl fmt::Display for Peptide {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+impl str::FromStr for Peptide {
+    type Err = AminoAcidError;
+
+    fn from_str(s: &str) -> Result<Peptide, AminoAcidError> {
+        Peptide::parse_from_slice(s.as_bytes())
+    }
+}
+
+/// Errors which can occur when attempting to interpret a sequence of
+/// `u8` as `Peptide`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AminoAcidError {
+    valid_up_to: usize,
+    byte: u8,
+}
+
+impl AminoAcidError {
+
+    /// Returns the index of the offending byte, the length of the valid
+    /// prefix of the input.
+    pub fn valid_up_to(&self) -> usize { self.valid_up_to }
+
+    /// The offending byte.
+    pub fn byte(&self) -> u8 { self.byte }
+
+}
+
+impl fmt::Display for AminoAcidError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.byte.is_ascii() {
+            write!(f, "invalid amino acid '{}' at index {}",
+                   (self.byte as char).escape_default(), self.valid_up_to)
+        } else {
+            write!(f, "invalid ascii: invalid byte 0x{:02x} at index {}", self.byte, self.valid_up_to)
+        }
+    }
+}
+
+impl error::Error for AminoAcidError {}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{Peptide, integer_mass, monoisotopic_mass};
+    use alphabet::{Alphabet, ProteinAlphabet};
+
+    #[test]
+    fn test_mass_tables() {
+        for &aa in ProteinAlphabet::SYMBOLS.iter() {
+            let (integer, mono) = (integer_mass(aa).unwrap(), monoisotopic_mass(aa).unwrap());
+            assert_eq!(integer, mono.floor() as u32, "{}", aa as char);
+        }
+        assert_eq!(integer_mass(b'X'), None);
+        assert_eq!(monoisotopic_mass(b'a'), None);
+    }
+
+    #[test]
+    fn test_prefix_masses() {
+        let peptide = Peptide::parse_from_slice(b"NQEL").unwrap();
+        assert_eq!(peptide.prefix_masses(), [0, 114, 242, 371, 484]);
+        let masses = peptide.monoisotopic_prefix_masses();
+        assert_eq!(masses.len(), 5);
+        assert!((masses[4] - peptide.monoisotopic_mass()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_parse() {
+        let err = "MKX*".parse::<Peptide>().unwrap_err();
+        assert_eq!((err.valid_up_to(), err.byte()), (2, b'X'));
+        assert_eq!(err.to_string(), "invalid amino acid 'X' at index 2");
+        assert_eq!(Peptide::parse_from_slice(b"").unwrap().mass(), 0);
+    }
+
+}
@@ -0,0 +1,302 @@
+//! NCBI genetic codes and translation of nucleotide sequences.
+//!
+//! Tables are written in the NCBI `gc.prt` order of codons, `TTT`, `TTC`,
+//! `TTA`, `TTG`, `TCT`, ... `GGG`. Codons may be written with `T` or `U`
+//! in any case.
+
+use ::dna::iupac;
+
+/// Genetic code, a mapping of codons to one-letter amino acid codes with
+/// `*` for stop codons, and the set of start codons.
+#[derive(Debug, PartialEq, Eq)]
+pub struct GeneticCode {
+    /// NCBI translation table number.
+    pub id: u8,
+    pub name: &'static str,
+    amino_acids: &'static [u8; 64],
+    starts: &'static [u8; 64],
+}
+
+/// The standard code, NCBI table 1.
+pub static STANDARD: GeneticCode = GeneticCode {
+    id: 1,
+    name: "Standard",
+    amino_acids: b"FFLLSSSSYY**CC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+    starts: b"---M------**--*----M---------------M----------------------------",
+};
+
+/// NCBI table 2.
+pub static VERTEBRATE_MITOCHONDRIAL: GeneticCode = GeneticCode {
+    id: 2,
+    name: "Vertebrate Mitochondrial",
+    amino_acids: b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNKKSS**VVVVAAAADDEEGGGG",
+    starts: b"----------**--------------------MMMM----------**---M------------",
+};
+
+/// NCBI table 3.
+pub static YEAST_MITOCHONDRIAL: GeneticCode = GeneticCode {
+    id: 3,
+    name: "Yeast Mitochondrial",
+    amino_acids: b"FFLLSSSSYY**CCWWTTTTPPPPHHQQRRRRIIMMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+    starts: b"----------**----------------------MM---------------M------------",
+};
+
+/// NCBI table 4.
+pub static MOLD_MITOCHONDRIAL: GeneticCode = GeneticCode {
+    id: 4,
+    name: "Mold, Protozoan, and Coelenterate Mitochondrial and Mycoplasma/Spiroplasma",
+    amino_acids: b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+    starts: b"--MM------**-------M------------MMMM---------------M------------",
+};
+
+/// NCBI table 5.
+pub static INVERTEBRATE_MITOCHONDRIAL: GeneticCode = GeneticCode {
+    id: 5,
+    name: "Invertebrate Mitochondrial",
+    amino_acids: b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNKKSSSSVVVVAAAADDEEGGGG",
+    starts: b"---M------**--------------------MMMM---------------M------------",
+};
+
+/// NCBI table 6.
+pub static CILIATE_NUCLEAR: GeneticCode = GeneticCode {
+    id: 6,
+    name: "Ciliate, Dasycladacean and Hexamita Nuclear",
+    amino_acids: b"FFLLSSSSYYQQCC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+    starts: b"--------------*--------------------M----------------------------",
+};
+
+/// NCBI table 9.
+pub static ECHINODERM_MITOCHONDRIAL: GeneticCode = GeneticCode {
+    id: 9,
+    name: "Echinoderm and Flatworm Mitochondrial",
+    amino_acids: b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNNKSSSSVVVVAAAADDEEGGGG",
+    starts: b"-----------------------------------M---------------M------------",
+};
+
+/// NCBI table 10.
+pub static EUPLOTID_NUCLEAR: GeneticCode = GeneticCode {
+    id: 10,
+    name: "Euplotid Nuclear",
+    amino_acids: b"FFLLSSSSYY**CCCWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+    starts: b"-----------------------------------M----------------------------",
+};
+
+/// Bacterial, archaeal and plant plastid code, NCBI table 11.
+pub static BACTERIAL: GeneticCode = GeneticCode {
+    id: 11,
+    name: "Bacterial, Archaeal and Plant Plastid",
+    amino_acids: b"FFLLSSSSYY**CC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+    starts: b"---M------**--*----M------------MMMM---------------M------------",
+};
+
+/// NCBI table 12.
+pub static ALTERNATIVE_YEAST_NUCLEAR: GeneticCode = GeneticCode {
+    id: 12,
+    name: "Alternative Yeast Nuclear",
+    amino_acids: b"FFLLSSSSYY**CC*WLLLSPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+    starts: b"-------------------M---------------M----------------------------",
+};
+
+/// NCBI table 13.
+pub static ASCIDIAN_MITOCHONDRIAL: GeneticCode = GeneticCode {
+    id: 13,
+    name: "Ascidian Mitochondrial",
+    amino_acids: b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNKKSSGGVVVVAAAADDEEGGGG",
+    starts: b"---M------------------------------MM---------------M------------",
+};
+
+/// NCBI table 14.
+pub static ALTERNATIVE_FLATWORM_MITOCHONDRIAL: GeneticCode = GeneticCode {
+    id: 14,
+    name: "Alternative Flatworm Mitochondrial",
+    amino_acids: b"FFLLSSSSYYY*CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNNKSSSSVVVVAAAADDEEGGGG",
+    starts: b"-----------------------------------M----------------------------",
+};
+
+/// All supported genetic codes.
+pub static CODES: [&GeneticCode; 12] = [
+    &STANDARD,
+    &VERTEBRATE_MITOCHONDRIAL,
+    &YEAST_MITOCHONDRIAL,
+    &MOLD_MITOCHONDRIAL,
+    &INVERTEBRATE_MITOCHONDRIAL,
+    &CILIATE_NUCLEAR,
+    &ECHINODERM_MITOCHONDRIAL,
+    &EUPLOTID_NUCLEAR,
+    &BACTERIAL,
+    &ALTERNATIVE_YEAST_NUCLEAR,
+    &ASCIDIAN_MITOCHONDRIAL,
+    &ALTERNATIVE_FLATWORM_MITOCHONDRIAL,
+];
+
+/// Bases in the order of the table index digits.
+static BASES: [u8; 4] = *b"TCAG";
+
+impl GeneticCode {
+
+    /// Genetic code with NCBI translation table number `id`.
+    pub fn from_id(id: u8) -> Option<&'static GeneticCode> {
+        CODES.iter().cloned().find(|code| code.id == id)
+    }
+
+    /// Table indices of all codons denoted by `codon`, which may contain
+    /// IUPAC ambiguity codes.
+    fn indices(codon: &[u8]) -> Vec<usize> {
+        let mut res = vec![0];
+        for &nuc in codon.iter() {
+            let bases = iupac::bases(nuc);
+            res = res.iter()
+                .flat_map(|&i| (0..4)
+                    .filter(move |&j| bases & iupac::bases(BASES[j]) != 0)
+                    .map(move |j| 4 * i + j))
+                .collect();
+        }
+        res
+    }
+
+    /// Amino acid encoded by `codon`, `*` for stop codons. Ambiguous
+    /// codons are translated if all the codons they denote encode the same
+    /// amino acid, otherwise, as well as for invalid codons, returns `X`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bio::rna::genetic_code::{STANDARD, VERTEBRATE_MITOCHONDRIAL};
+    ///
+    /// assert_eq!(STANDARD.translate_codon(b"AUG"), b'M');
+    /// assert_eq!(STANDARD.translate_codon(b"TGA"), b'*');
+    /// assert_eq!(VERTEBRATE_MITOCHONDRIAL.translate_codon(b"UGA"), b'W');
+    /// assert_eq!(STANDARD.translate_codon(b"GGN"), b'G');
+    /// assert_eq!(STANDARD.translate_codon(b"NNN"), b'X');
+    /// ```
+    pub fn translate_codon(&self, codon: &[u8]) -> u8 {
+        if codon.len() != 3 {
+            return b'X';
+        }
+        let mut amino_acids = GeneticCode::indices(codon).into_iter().map(|i| self.amino_acids[i]);
+        match amino_acids.next() {
+            Some(aa) if amino_acids.all(|a| a == aa) => aa,
+            _ => b'X',
+        }
+    }
+
+    /// Returns `true` if `codon` is a start codon, including alternative
+    /// start codons such as `GUG` in the bacterial code.
+    pub fn is_start(&self, codon: &[u8]) -> bool {
+        let indices = GeneticCode::indices(codon);
+        codon.len() == 3 && !indices.is_empty() && indices.iter().all(|&i| self.starts[i] == b'M')
+    }
+
+    pub fn is_stop(&self, codon: &[u8]) -> bool {
+        self.translate_codon(codon) == b'*'
+    }
+
+}
+
+/// Translation of nucleotide sequences with a genetic code.
+///
+/// By default all codons of the first reading frame are translated, stop
+/// codons as `*`, and the trailing incomplete codon is ignored.
+///
+/// # Examples
+///
+/// ```
+/// use bio::rna::Translator;
+/// use bio::rna::genetic_code::BACTERIAL;
+///
+/// let gene = b"GTGAAACGCATTAGCACCACCTAAGGA";
+/// assert_eq!(Translator::new(&BACTERIAL).translate(gene), "VKRISTT*G");
+/// assert_eq!(Translator::new(&BACTERIAL).cds().translate(gene), "MKRISTT");
+/// assert_eq!(Translator::new(&BACTERIAL).frame(1).translate(gene), "*NALAPPK");
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Translator<'a> {
+    code: &'a GeneticCode,
+    frame: usize,
+    to_stop: bool,
+    cds: bool,
+}
+
+impl<'a> Translator<'a> {
+
+    pub fn new(code: &'a GeneticCode) -> Translator<'a> {
+        Translator { code, frame: 0, to_stop: false, cds: false }
+    }
+
+    /// Translate starting at offset `frame`, 0, 1 or 2.
+    pub fn frame(mut self, frame: usize) -> Translator<'a> {
+        assert!(frame < 3, "reading frame should be 0, 1 or 2");
+        self.frame = frame;
+        self
+    }
+
+    /// Stop translation at the first stop codon, which is not included.
+    pub fn to_stop(mut self) -> Translator<'a> {
+        self.to_stop = true;
+        self
+    }
+
+    /// Translate a coding sequence: stop at the first stop codon and
+    /// translate the first codon as `M` if it is an alternative start
+    /// codon.
+    pub fn cds(mut self) -> Translator<'a> {
+        self.cds = true;
+        self.to_stop = true;
+        self
+    }
+
+    /// Translates `seq`, DNA or RNA, into one-letter amino acid codes.
+    pub fn translate(&self, seq: &[u8]) -> String {
+        let codons = seq[self.frame.min(seq.len())..].chunks(3).filter(|c| c.len() == 3);
+        let mut protein = String::with_capacity(seq.len() / 3);
+        for (i, codon) in codons.enumerate() {
+            let aa = if i == 0 && self.cds && self.code.is_start(codon) {
+                b'M'
+            } else {
+                self.code.translate_codon(codon)
+            };
+            if aa == b'*' && self.to_stop {
+                break;
+            }
+            protein.push(aa as char);
+        }
+        protein
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{CODES, GeneticCode, Translator, BACTERIAL, STANDARD};
+    use rna::Rna;
+
+    #[test]
+    fn test_tables() {
+        for code in CODES.iter() {
+            assert_eq!(GeneticCode::from_id(code.id), Some(*code));
+            assert!(code.is_start(b"ATG"), "{}", code.name);
+            assert!(code.amino_acids.iter().all(|aa| aa.is_ascii_uppercase() || *aa == b'*'));
+            assert!(code.starts.iter().all(|s| b"-*M".contains(s)));
+        }
+        assert_eq!(GeneticCode::from_id(7), None);
+    }
+
+    #[test]
+    fn test_starts() {
+        assert!(BACTERIAL.is_start(b"GTG"));
+        assert!(BACTERIAL.is_start(b"auu"));
+        assert!(!STANDARD.is_start(b"GTG"));
+        assert!(!STANDARD.is_start(b"ATN"));
+        assert!(!STANDARD.is_start(b"AT"));
+    }
+
+    #[test]
+    fn test_local_code() {
+        // built on the stack, so not borrowed for 'static
+        let local = GeneticCode { id: 0, name: "Local", ..BACTERIAL };
+        assert_eq!(Translator::new(&local).cds().translate(b"GTGTGA"), "M");
+        assert_eq!(Rna::from_slice(b"GUGUGA").translate(&local), "V*");
+    }
+
+}
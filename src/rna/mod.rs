@@ -0,0 +1,148 @@
+//! RNA sequences, transcription and translation
+
+pub mod genetic_code;
+
+use std::fmt;
+use std::ops;
+use std::str;
+
+use ::dna::{iupac, AsciiError, Dna};
+
+pub use self::genetic_code::{GeneticCode, Translator};
+
+pub const A: u8 = b'A';
+pub const U: u8 = b'U';
+pub const G: u8 = b'G';
+pub const C: u8 = b'C';
+
+/// RNA abstraction over a byte vector, the counterpart of `Dna` with `U`
+/// in place of `T`.
+///
+/// # Examples
+///
+/// ```
+/// use bio::dna::Dna;
+/// use bio::rna::Rna;
+/// use bio::rna::genetic_code::STANDARD;
+///
+/// let mrna = Rna::transcribe(&Dna::from_str("ATGGCCTAA"));
+/// assert_eq!(mrna.as_str(), "AUGGCCUAA");
+/// assert_eq!(mrna.translate(&STANDARD), "MA*");
+/// assert_eq!(mrna.to_dna().as_str(), "ATGGCCTAA");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Rna {
+    vec: Vec<u8>,
+}
+
+impl Rna {
+
+    pub fn new(vec: Vec<u8>) -> Rna {
+        Rna { vec }
+    }
+
+    pub fn from_slice(s: &[u8]) -> Rna {
+        Rna { vec: s.to_vec() }
+    }
+
+    /// Creates `Rna` from bytes, checking that every byte is one of `A`,
+    /// `C`, `G` and `U`.
+    pub fn parse_from_slice(s: &[u8]) -> Result<Rna, AsciiError> {
+        match s.iter().position(|&b| !matches!(b, A | C | G | U)) {
+            Some(i) => Err(AsciiError::new(i, s[i])),
+            None => Ok(Rna::from_slice(s)),
+        }
+    }
+
+    /// Transcribes `dna` coding strand, replacing `T` with `U` and keeping
+    /// the case.
+    pub fn transcribe(dna: &Dna) -> Rna {
+        Rna::new(dna.iter().map(|&b| match b {
+            b'T' => U,
+            b't' => b'u',
+            b => b,
+        }).collect())
+    }
+
+    /// Reverse transcription, replacing `U` with `T`.
+    pub fn to_dna(&self) -> Dna {
+        Dna::new(self.vec.iter().map(|&b| match b {
+            U => b'T',
+            b'u' => b't',
+            b => b,
+        }).collect())
+    }
+
+    pub fn as_str(&self) -> &str {
+        str::from_utf8(&self.vec).expect("RNA should be ASCII")
+    }
+
+    /// Return reverse complement of the RNA string, IUPAC ambiguity codes
+    /// are complemented too.
+    pub fn reverse_complement(&self) -> Rna {
+        Rna::new(self.vec.iter().rev().map(|&b| match iupac::complement(b) {
+            b'T' => U,
+            b't' => b'u',
+            c => c,
+        }).collect())
+    }
+
+    /// Translates the first reading frame with genetic `code`, see
+    /// `Translator` for other frames and coding sequences.
+    pub fn translate(&self, code: &GeneticCode) -> String {
+        Translator::new(code).translate(self)
+    }
+
+}
+
+impl AsRef<[u8]> for Rna {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl ops::Deref for Rna {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.vec
+    }
+}
+
+impl fmt::Display for Rna {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+impl str::FromStr for Rna {
+    type Err = AsciiError;
+
+    fn from_str(s: &str) -> Result<Rna, AsciiError> {
+        Rna::parse_from_slice(s.as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::Rna;
+    use dna::Dna;
+
+    #[test]
+    fn test_transcription() {
+        let dna = Dna::from_str("ATGacgtNT");
+        let rna = Rna::transcribe(&dna);
+        assert_eq!(rna.as_str(), "AUGacguNU");
+        assert_eq!(rna.to_dna(), dna);
+        assert_eq!(rna.reverse_complement().to_dna(), dna.reverse_complement());
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("ACGU".parse::<Rna>().unwrap().as_str(), "ACGU");
+        let err = "ACGT".parse::<Rna>().unwrap_err();
+        assert_eq!((err.valid_up_to(), err.byte()), (3, b'T'));
+    }
+
+}
@@ -0,0 +1,201 @@
+//! Aho-Corasick automaton for exact search of many patterns at once.
+
+use std::collections::VecDeque;
+
+/// State id of the root of the trie.
+const ROOT: usize = 0;
+
+/// Matcher of a set of patterns, finding all occurrences of all patterns
+/// in a single pass over a text, in time linear in the length of the text
+/// and the number of hits.
+///
+/// The automaton is a deterministic one, with a transition for every state
+/// and byte class, bytes which do not occur in patterns share one class.
+///
+/// # Examples
+///
+/// ```
+/// use bio::dna::Dna;
+/// use bio::seq::AhoCorasick;
+///
+/// let boxes = AhoCorasick::new(&["TATAAT", "TTGACA", "ATAA"]);
+/// let dna = Dna::from_str("GTTGACATTTATAATG");
+/// assert_eq!(boxes.find(&dna), [(1, 1), (2, 10), (0, 9)]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct AhoCorasick {
+    /// Byte class of every byte, 0 for bytes not in any pattern.
+    classes: Vec<usize>,
+    /// Number of byte classes, the row length of `delta`.
+    stride: usize,
+    /// Transitions, `delta[state * stride + class]`.
+    delta: Vec<usize>,
+    /// Ids of patterns ending at every state, longest first.
+    outputs: Vec<Vec<usize>>,
+    /// Lengths of patterns.
+    lens: Vec<usize>,
+}
+
+impl AhoCorasick {
+
+    /// Builds the automaton for `patterns`, identified by their indices.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any pattern is empty.
+    pub fn new<P: AsRef<[u8]>>(patterns: &[P]) -> AhoCorasick {
+        let mut classes = vec![0; 256];
+        let mut stride = 1;
+        for pattern in patterns.iter() {
+            assert!(!pattern.as_ref().is_empty(), "patterns should not be empty");
+            for &b in pattern.as_ref().iter() {
+                if classes[b as usize] == 0 {
+                    classes[b as usize] = stride;
+                    stride += 1;
+                }
+            }
+        }
+
+        // trie, with `ROOT` for missing transitions, as the root is never
+        // a target of other transitions
+        let mut delta = vec![ROOT; stride];
+        let mut outputs = vec![Vec::new()];
+        for (id, pattern) in patterns.iter().enumerate() {
+            let mut state = ROOT;
+            for &b in pattern.as_ref().iter() {
+                let i = state * stride + classes[b as usize];
+                if delta[i] == ROOT {
+                    delta[i] = outputs.len();
+                    delta.extend((0..stride).map(|_| ROOT));
+                    outputs.push(Vec::new());
+                }
+                state = delta[i];
+            }
+            outputs[state].push(id);
+        }
+
+        // failure links in breadth first order turn the trie into a
+        // deterministic automaton, transitions of the failure state of a
+        // state are complete by the time it is visited
+        let mut fail = vec![ROOT; outputs.len()];
+        let mut queue: VecDeque<_> = delta[..stride].iter()
+            .cloned()
+            .filter(|&next| next != ROOT)
+            .collect();
+        while let Some(state) = queue.pop_front() {
+            let f = fail[state];
+            let inherited = outputs[f].clone();
+            outputs[state].extend(inherited);
+            for c in 0..stride {
+                let i = state * stride + c;
+                let next = delta[i];
+                if next == ROOT {
+                    delta[i] = delta[f * stride + c];
+                } else {
+                    fail[next] = delta[f * stride + c];
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        let lens = patterns.iter().map(|p| p.as_ref().len()).collect();
+        AhoCorasick { classes, stride, delta, outputs, lens }
+    }
+
+    /// Number of patterns.
+    pub fn len(&self) -> usize {
+        self.lens.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lens.is_empty()
+    }
+
+    /// Length of pattern `id`.
+    pub fn pattern_len(&self, id: usize) -> usize {
+        self.lens[id]
+    }
+
+    /// Iterator over `(pattern id, position)` pairs of all occurrences of
+    /// patterns in `text`, in the order of their end positions.
+    pub fn find_iter<'a>(&'a self, text: &'a [u8]) -> Matches<'a> {
+        Matches { ac: self, text, pos: 0, state: ROOT, output: 0 }
+    }
+
+    /// All occurrences of patterns in `text`, see `find_iter`.
+    pub fn find(&self, text: &[u8]) -> Vec<(usize, usize)> {
+        self.find_iter(text).collect()
+    }
+
+    /// Returns `true` if any pattern occurs in `text`.
+    pub fn is_match(&self, text: &[u8]) -> bool {
+        self.find_iter(text).next().is_some()
+    }
+
+}
+
+/// Iterator over pattern occurrences, see `AhoCorasick::find_iter`.
+pub struct Matches<'a> {
+    ac: &'a AhoCorasick,
+    text: &'a [u8],
+    /// Number of bytes of the text read.
+    pos: usize,
+    state: usize,
+    /// Index of the next output of `state` to report.
+    output: usize,
+}
+
+impl<'a> Iterator for Matches<'a> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<(usize, usize)> {
+        let ac = self.ac;
+        loop {
+            if let Some(&id) = ac.outputs[self.state].get(self.output) {
+                self.output += 1;
+                return Some((id, self.pos - ac.lens[id]));
+            }
+            let &b = self.text.get(self.pos)?;
+            self.state = ac.delta[self.state * ac.stride + ac.classes[b as usize]];
+            self.pos += 1;
+            self.output = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::AhoCorasick;
+    use seq;
+
+    fn naive(patterns: &[&[u8]], text: &[u8]) -> Vec<(usize, usize)> {
+        let mut hits: Vec<_> = patterns.iter()
+            .enumerate()
+            .flat_map(|(id, p)| seq::find(text, p).into_iter().map(move |pos| (id, pos)))
+            .collect();
+        hits.sort_by_key(|&(id, pos)| (pos + patterns[id].len(), pos));
+        hits
+    }
+
+    #[test]
+    fn test_find() {
+        let patterns: &[&[u8]] = &[b"he", b"she", b"his", b"hers", b"e"];
+        let ac = AhoCorasick::new(patterns);
+        assert_eq!(ac.len(), 5);
+        for &text in [&b"ushers"[..], b"", b"hishehershe", b"xyz", b"eeeshe"].iter() {
+            assert_eq!(ac.find(text), naive(patterns, text));
+        }
+    }
+
+    #[test]
+    fn test_dna() {
+        let patterns: &[&[u8]] = &[b"ACA", b"CAC", b"ACACA", b"A", b"TTT", b"ACA"];
+        let ac = AhoCorasick::new(patterns);
+        let text = b"ACACACATTTTGACACANACAC";
+        assert_eq!(ac.find(text), naive(patterns, text));
+        assert!(!AhoCorasick::new(&["GGG"]).is_match(text));
+        assert!(AhoCorasick::new(&[] as &[&str]).find(text).is_empty());
+    }
+
+}
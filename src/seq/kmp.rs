@@ -0,0 +1,134 @@
+//! Knuth-Morris-Pratt exact search of a single pattern.
+
+use std::borrow::Cow;
+
+/// Matcher of a pattern, preprocessed once in `O(m)` time and then
+/// searched for in any number of texts in `O(n)` amortised time, for a
+/// pattern of length `m` and a text of length `n`.
+///
+/// # Examples
+///
+/// ```
+/// use bio::seq::Kmp;
+///
+/// let kmp = Kmp::new(b"ATAT");
+/// assert_eq!(kmp.find(b"GATATATCATAT"), [1, 3, 8]);
+///
+/// let mut hits = kmp.find_iter(b"ATATAT");
+/// assert_eq!(hits.next(), Some(0));
+/// assert_eq!(hits.next(), Some(2));
+/// assert_eq!(hits.next(), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Kmp<'a, T: 'a> {
+    pattern: &'a [T],
+    /// `border[i]` is the length of the longest proper border, both prefix
+    /// and suffix, of `pattern[..i + 1]`.
+    border: Vec<usize>,
+}
+
+impl<'a, T: PartialEq> Kmp<'a, T> {
+
+    pub fn new(pattern: &'a [T]) -> Kmp<'a, T> {
+        let mut border = vec![0; pattern.len()];
+        let mut b = 0;
+        for i in 1..pattern.len() {
+            while b > 0 && pattern[i] != pattern[b] {
+                b = border[b - 1];
+            }
+            if pattern[i] == pattern[b] {
+                b += 1;
+            }
+            border[i] = b;
+        }
+        Kmp { pattern, border }
+    }
+
+    /// Lazy iterator over start positions of all, possibly overlapping,
+    /// occurrences of the pattern in `text`. An empty pattern has no
+    /// occurrences.
+    pub fn find_iter<'b>(&'b self, text: &'b [T]) -> KmpMatches<'b, T> {
+        KmpMatches::new(self.pattern, Cow::Borrowed(&self.border), text)
+    }
+
+    /// Lazy iterator over occurrences in `text`, owning the matcher.
+    pub fn into_find_iter(self, text: &'a [T]) -> KmpMatches<'a, T> {
+        KmpMatches::new(self.pattern, Cow::Owned(self.border), text)
+    }
+
+    /// Start positions of all occurrences of the pattern in `text`, see
+    /// `find_iter`.
+    pub fn find(&self, text: &[T]) -> Vec<usize> {
+        self.find_iter(text).collect()
+    }
+
+}
+
+/// Iterator over occurrences of a pattern, see `Kmp::find_iter`.
+pub struct KmpMatches<'a, T: 'a> {
+    pattern: &'a [T],
+    border: Cow<'a, [usize]>,
+    text: &'a [T],
+    /// Position of the next element of the text to compare.
+    pos: usize,
+    /// Length of the pattern prefix matched before `pos`.
+    matched: usize,
+}
+
+impl<'a, T> KmpMatches<'a, T> {
+
+    fn new(pattern: &'a [T], border: Cow<'a, [usize]>, text: &'a [T]) -> KmpMatches<'a, T> {
+        KmpMatches { pattern, border, text, pos: 0, matched: 0 }
+    }
+
+}
+
+impl<'a, T: PartialEq> Iterator for KmpMatches<'a, T> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let (pattern, border) = (self.pattern, &self.border);
+        if pattern.is_empty() {
+            return None;
+        }
+        while self.pos < self.text.len() {
+            let x = &self.text[self.pos];
+            while self.matched > 0 && *x != pattern[self.matched] {
+                self.matched = border[self.matched - 1];
+            }
+            if *x == pattern[self.matched] {
+                self.matched += 1;
+            }
+            self.pos += 1;
+            if self.matched == pattern.len() {
+                self.matched = border[self.matched - 1];
+                return Some(self.pos - pattern.len());
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::Kmp;
+
+    fn naive(text: &[u8], pattern: &[u8]) -> Vec<usize> {
+        (0..(text.len() + 1).saturating_sub(pattern.len()))
+            .filter(|&i| &text[i..i + pattern.len()] == pattern)
+            .collect()
+    }
+
+    #[test]
+    fn test_find() {
+        let text = b"AABAACAADAABAABAAAAAABAAB";
+        for &pattern in [&b"AABA"[..], b"A", b"AAAA", b"AABAAB", b"C", b"G", b"AABAACAADAABAABAAAAAABAAB"].iter() {
+            assert_eq!(Kmp::new(pattern).find(text), naive(text, pattern));
+        }
+        assert!(Kmp::new(b"AAB").find(b"AA").is_empty());
+        assert!(Kmp::new(b"").find(b"AC").is_empty());
+        assert_eq!(Kmp::new(&[1, 2, 1]).find(&[1, 2, 1, 2, 1]), [0, 2]);
+    }
+
+}
@@ -0,0 +1,380 @@
+//! Algorightms on sequences
+
+mod aho_corasick;
+mod kmp;
+mod myers;
+
+use std::borrow::Cow;
+use std::iter;
+use std::slice;
+
+use data;
+use data::stream::Chunk;
+
+pub use self::aho_corasick::{AhoCorasick, Matches};
+pub use self::kmp::{Kmp, KmpMatches};
+pub use self::myers::{Alignment, Edit, Myers, MyersMatches};
+
+/// Search for occurrences of `pattern` in `text`. Returns indices
+/// of the first character of all `text` slices that matches the `pattern`,
+/// in linear time with `Kmp`. An empty pattern has no occurrences. See
+/// `AhoCorasick` to search for many patterns at once.
+///
+/// # Examples
+///
+/// ```
+/// use bio::seq::find;
+///
+/// assert_eq!(find(b"GATATATGCATATAC", b"ATAT"), [1, 3, 9]);
+/// assert!(find(b"AT", b"ATAT").is_empty());
+/// assert!(find(b"AT", b"").is_empty());
+/// ```
+pub fn find<T: PartialEq>(text: &[T], pattern: &[T]) -> Vec<usize> {
+    Kmp::new(pattern).find(text)
+}
+
+/// Lazy version of `find`, for huge texts, such as memory mapped genomes.
+///
+/// # Examples
+///
+/// ```
+/// use bio::seq::find_iter;
+///
+/// let first = find_iter(b"GATATATGCATATAC", b"ATAT").next();
+/// assert_eq!(first, Some(1));
+/// ```
+pub fn find_iter<'a, T: PartialEq>(text: &'a [T], pattern: &'a [T]) -> KmpMatches<'a, T> {
+    Kmp::new(pattern).into_find_iter(text)
+}
+
+/// Fuzzy search of a `pattern` in `text` by a given `compare` function.
+/// Returns a pair containing vector of indices and the vector of matched slices.
+/// Matched slices are as long as the pattern, see `Myers` to allow insertions
+/// and deletions. An empty pattern has no matches.
+pub fn find_by<'a, 'b, F, T>(text: &'a [T], pattern: &'b [T], compare: F) -> (Vec<usize>, Vec<&'a [T]>)
+    where F: Fn(&[T], &[T]) -> bool,
+{
+    if pattern.is_empty() {
+        return (Vec::new(), Vec::new());
+    }
+    text.windows(pattern.len())
+        .enumerate()
+        .filter(|&(_, chunk)| compare(chunk, pattern))
+        .unzip()
+}
+
+/// Circular view of a sequence, such as a bacterial chromosome, where the
+/// last element is followed by the first one. Windows and matches may
+/// span the end of the sequence, positions are always less than its
+/// length.
+///
+/// # Examples
+///
+/// ```
+/// use bio::seq::Circular;
+///
+/// let genome = Circular::new(b"TACGTA");
+/// assert_eq!(genome.find(b"TAT"), [4]);
+/// assert_eq!(&genome.window(5, 3)[..], b"ATA");
+/// assert_eq!(genome.windows(4).count(), 6);
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct Circular<'a, T: 'a> {
+    seq: &'a [T],
+}
+
+impl<'a, T> Clone for Circular<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for Circular<'a, T> {}
+
+impl<'a, T: Clone> Circular<'a, T> {
+
+    pub fn new(seq: &'a [T]) -> Circular<'a, T> {
+        Circular { seq }
+    }
+
+    pub fn len(&self) -> usize {
+        self.seq.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.seq.is_empty()
+    }
+
+    /// Element at position `i` modulo length.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sequence is empty.
+    pub fn get(&self, i: usize) -> &'a T {
+        &self.seq[i % self.len()]
+    }
+
+    /// Iterator over all elements once, starting at `start` modulo length
+    /// and wrapping around the end.
+    pub fn iter_from(&self, start: usize) -> iter::Chain<slice::Iter<'a, T>, slice::Iter<'a, T>> {
+        let (head, tail) = self.seq.split_at(if self.is_empty() { 0 } else { start % self.len() });
+        tail.iter().chain(head.iter())
+    }
+
+    /// Window of length `k` starting at `i` modulo length. Only windows
+    /// wrapping around the end are copied.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k > 0` and the sequence is empty.
+    pub fn window(&self, i: usize, k: usize) -> Cow<'a, [T]> {
+        if k == 0 {
+            return Cow::Borrowed(&[]);
+        }
+        let i = i % self.len();
+        if i + k <= self.len() {
+            Cow::Borrowed(&self.seq[i..i + k])
+        } else {
+            Cow::Owned(self.seq.iter().cycle().skip(i).take(k).cloned().collect())
+        }
+    }
+
+    /// Windows of length `k` starting at every position with their
+    /// positions, `len()` windows in total.
+    pub fn windows(&self, k: usize) -> Box<dyn Iterator<Item = (usize, Cow<'a, [T]>)> + 'a> {
+        let view = *self;
+        Box::new((0..self.len()).map(move |i| (i, view.window(i, k))))
+    }
+
+    /// Linear copy of the sequence followed by its first `m` elements,
+    /// wrapping around as many times as needed. Linear windows of length
+    /// `m + 1` of the copy are all circular windows.
+    pub fn extended(&self, m: usize) -> Vec<T> {
+        let len = if self.is_empty() { 0 } else { self.len() + m };
+        self.seq.iter().cycle().take(len).cloned().collect()
+    }
+
+    /// Search for occurrences of `pattern`, including those spanning the
+    /// end of the sequence, in linear time with `Kmp`. An empty pattern
+    /// has no occurrences.
+    pub fn find(&self, pattern: &[T]) -> Vec<usize>
+        where T: PartialEq,
+    {
+        if pattern.is_empty() {
+            return Vec::new();
+        }
+        let text = self.extended(pattern.len() - 1);
+        Kmp::new(pattern).find(&text)
+    }
+
+    /// Fuzzy search of a `pattern` by a given `compare` function, as
+    /// `find_by`, including matches spanning the end of the sequence. An
+    /// empty pattern has no matches.
+    pub fn find_by<F>(&self, pattern: &[T], compare: F) -> Vec<usize>
+        where F: Fn(&[T], &[T]) -> bool,
+    {
+        if pattern.is_empty() {
+            return Vec::new();
+        }
+        let text = self.extended(pattern.len() - 1);
+        let (inds, _) = find_by(&text, pattern, compare);
+        inds
+    }
+
+}
+
+/// Search for occurrences of `pattern` in a stream of sequence chunks.
+/// Returns pairs of record index and position within the record. An empty
+/// pattern has no occurrences, as in `find`. Chunks should overlap by at
+/// least `pattern.len() - 1` bases.
+///
+/// # Examples
+///
+/// ```
+/// use bio::data::stream::Chunks;
+/// use bio::seq::find_chunks;
+///
+/// let input = b">r1\nACGTTACG\n>r2\nTTACG\n";
+/// let chunks = Chunks::new(&input[..], 4, 2);
+/// assert_eq!(find_chunks(chunks, b"ACG").unwrap(), [(0, 0), (0, 5), (1, 2)]);
+///
+/// let chunks = Chunks::new(&input[..], 4, 2);
+/// assert!(find_chunks(chunks, b"").unwrap().is_empty());
+/// ```
+pub fn find_chunks<I>(chunks: I, pattern: &[u8]) -> data::Result<Vec<(usize, usize)>>
+    where I: IntoIterator<Item = data::Result<Chunk>>,
+{
+    let mut res = Vec::new();
+    for chunk in chunks {
+        let chunk = chunk?;
+        let hits = chunk.windows(pattern.len())
+            .filter(|&(_, w)| w == pattern)
+            .map(|(i, _)| (chunk.record, i));
+        res.extend(hits);
+    }
+    Ok(res)
+}
+
+/// Compute Hamming distance between two slices.
+///
+/// # Examples
+///
+/// ```
+/// use bio::seq::hamming_distance;
+///
+/// let dx = "GGGCCGTTGGT";
+/// let dy = "GGACCGTTGAC";
+/// assert_eq!(hamming_distance(dx.as_bytes(), dy.as_bytes()), 3);
+/// ```
+///
+/// # Panics
+///
+/// Panics when input sequences has different length.
+pub fn hamming_distance<I, J, T>(xs: I, ys: J) -> usize
+    where I: IntoIterator<Item = T>,
+          J: IntoIterator<Item = T>,
+          T: PartialEq,
+{
+    xs.into_iter()
+        .zip(ys.into_iter())
+        .fold(0, |acc, (x, y)| if x == y { acc } else { acc + 1 })
+}
+
+/// Returns indices of minimum elements.
+///
+/// # Examples
+///
+/// ```
+/// use bio::seq::min_indices;
+///
+/// let empty: Vec<u8> = Vec::new();
+///
+/// assert_eq!(min_indices(vec![1, 3, 1, 2]), [0, 2]);
+/// assert_eq!(min_indices(empty), []);
+/// ```
+pub fn min_indices<I, T>(iter: I) -> Vec<usize>
+    where I: IntoIterator<Item = T>,
+          T: PartialOrd + PartialEq,
+{
+    let mut enumerated = iter.into_iter().enumerate();
+    match enumerated.next() {
+        Some((i, x)) => min_inds(enumerated, vec![i], x),
+        None => Vec::new(),
+    }
+}
+
+fn min_inds<I, T>(enumerated: I, min_indices: Vec<usize>, local_min: T) -> Vec<usize>
+    where I: Iterator<Item = (usize, T)>,
+          T: PartialOrd + PartialEq,
+{
+    let mut min = local_min;
+    let mut inds = min_indices;
+
+    for (i, x) in enumerated {
+        if x < min {
+            inds.clear();
+            inds.push(i);
+            min = x;
+        } else if x == min {
+            inds.push(i);
+        }
+    }
+
+    inds
+}
+
+/// Returns all permutations of length `n` for elements in `xs`.
+///
+/// # Examples
+///
+/// ```
+/// use bio::seq::permutations_with_repetitions;
+///
+/// let input = [0, 1];
+/// let expected = [vec![0, 0], vec![0, 1], vec![1, 0], vec![1, 1]];
+/// let res: Vec<_> = permutations_with_repetitions(&input, 2).collect();
+/// assert_eq!(res, expected);
+/// ```
+pub fn permutations_with_repetitions<'a, T>(xs: &'a [T], n: usize) -> Box<Iterator<Item = Vec<T>> + 'a>
+    where T: Clone,
+{
+    match n {
+        0 => Box::new(iter::empty()),
+        1 => {
+            let it = xs.into_iter()
+                .map(|t| vec![t.clone()]);
+            Box::new(it)
+        },
+        _ => {
+            let it = xs.into_iter()
+                .flat_map(move |t| {
+                    permutations_with_repetitions(xs.clone(), n - 1)
+                        .map(move |perm| [vec![t.clone()], perm].concat())
+                });
+            Box::new(it)
+        },
+    }
+}
+
+/// Returns concatenation of two slices.
+///
+/// # Example
+///
+/// ```
+/// use bio::seq::concat;
+///
+/// let xs = [1, 2];
+/// let ys = [3, 4];
+/// assert_eq!(vec![1, 2, 3, 4], concat(&xs, &ys));
+/// ```
+#[inline]
+pub fn concat<T: Clone>(xs: &[T], ys: &[T]) -> Vec<T> {
+    let mut v = Vec::with_capacity(xs.len() + ys.len());
+    v.extend(xs.iter().cloned());
+    v.extend(ys.iter().cloned());
+    v
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use test::Bencher;
+    use data::Dataset;
+
+    #[test]
+    fn test_circular() {
+        let genome = Circular::new(b"ACGTTA");
+        let windows: Vec<_> = genome.windows(3).map(|(i, w)| (i, w.into_owned())).collect();
+        assert_eq!(windows[4], (4, b"TAA".to_vec()));
+        assert_eq!(windows[5], (5, b"AAC".to_vec()));
+        assert_eq!(genome.find(b"TAAC"), [4]);
+        assert_eq!(genome.find(b"ACGTTAACG"), [0]);
+        assert_eq!(genome.find_by(b"AAA", |a, b| hamming_distance(a, b) <= 1), [4, 5]);
+        let rotated: Vec<_> = genome.iter_from(8).cloned().collect();
+        assert_eq!(rotated, b"GTTAAC");
+        assert!(Circular::new(b"").find(b"A").is_empty());
+        assert!(genome.find(b"").is_empty());
+        let repeat = Circular::new(b"ATATA");
+        assert_eq!(repeat.find(b"ATA"), repeat.find_by(b"ATA", |a, b| a == b));
+        assert!(genome.find_by(b"", |_, _| true).is_empty());
+    }
+
+    #[bench]
+    fn bench_hamming_distance(b: &mut Bencher) {
+        let dataset = Dataset::open_text("data/bioinformatics1/hamming_distance/dataset_9_3.txt");
+        let lines = dataset.lines();
+        let (da, db) = (lines[0].as_bytes(), lines[1].as_bytes());
+        b.iter(|| hamming_distance(da, db));
+    }
+
+    #[bench]
+    fn bench_find(b: &mut Bencher) {
+        let dataset = Dataset::open_text("data/bioinformatics1/pattern_count/dataset_2_7.txt");
+        let lines = dataset.lines();
+        let (text, pattern) = (lines[0].as_bytes(), lines[1].as_bytes());
+        b.iter(|| find(text, pattern));
+    }
+}
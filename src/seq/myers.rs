@@ -0,0 +1,272 @@
+//! Approximate search of a pattern under edit (Levenshtein) distance with
+//! Myers' bit-parallel algorithm.
+
+/// Maximal pattern length, the number of bits of a word.
+pub const MAX_LEN: usize = 64;
+
+/// Matcher of a pattern, finding all end positions in a text where the
+/// pattern occurs with at most `k` substitutions, insertions and
+/// deletions, in `O(n)` time for a text of length `n`.
+///
+/// Unlike `find_by` with `hamming_distance`, occurrences may be shorter or
+/// longer than the pattern.
+///
+/// # Examples
+///
+/// ```
+/// use bio::seq::{Edit, Myers};
+///
+/// let myers = Myers::new(b"TATAAT");
+/// // TATAAT with one base deleted
+/// let text = b"GCGTTATATGC";
+/// assert_eq!(myers.find(text, 1), [(9, 1)]);
+///
+/// let alignment = myers.alignment(text, 9);
+/// assert_eq!((alignment.start, alignment.dist), (4, 1));
+/// assert_eq!(alignment.ops[3], Edit::Del);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Myers {
+    pattern: Vec<u8>,
+    /// Bit `i` of `peq[b]` is set if `pattern[i] == b`.
+    peq: [u64; 256],
+}
+
+impl Myers {
+
+    /// # Panics
+    ///
+    /// Panics if the pattern is empty or longer than `MAX_LEN`.
+    pub fn new(pattern: &[u8]) -> Myers {
+        assert!(!pattern.is_empty(), "pattern should not be empty");
+        assert!(pattern.len() <= MAX_LEN, "pattern should be at most {} long", MAX_LEN);
+        let mut peq = [0; 256];
+        for (i, &b) in pattern.iter().enumerate() {
+            peq[b as usize] |= 1 << i;
+        }
+        Myers { pattern: pattern.to_vec(), peq }
+    }
+
+    /// Iterator over `(end, distance)` pairs for every end position in
+    /// `text`, exclusive, where the pattern matches a text slice with at
+    /// most `k` edits. Overlapping occurrences ending at consecutive
+    /// positions are all reported.
+    pub fn find_iter<'a>(&'a self, text: &'a [u8], k: usize) -> MyersMatches<'a> {
+        MyersMatches {
+            myers: self,
+            text,
+            k,
+            pos: 0,
+            pv: !0,
+            mv: 0,
+            dist: self.pattern.len(),
+        }
+    }
+
+    /// All `(end, distance)` pairs, see `find_iter`.
+    pub fn find(&self, text: &[u8], k: usize) -> Vec<(usize, usize)> {
+        self.find_iter(text, k).collect()
+    }
+
+    /// Best alignment of the pattern to a slice of `text` ending at `end`,
+    /// exclusive. Among alignments of minimal distance, the one with
+    /// matches and substitutions first when read from the end is chosen.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `end > text.len()`.
+    pub fn alignment(&self, text: &[u8], end: usize) -> Alignment {
+        let m = self.pattern.len();
+        // the distance is at most m, so the slice is at most 2m long
+        let offset = end.saturating_sub(2 * m);
+        let window = &text[offset..end];
+        let n = window.len();
+
+        // dist[i][j] of pattern[..i] to the best slice of window ending at
+        // j, free to start anywhere
+        let mut dist = vec![vec![0; n + 1]; m + 1];
+        for i in 1..m + 1 {
+            dist[i][0] = i;
+            for j in 1..n + 1 {
+                let diag = dist[i - 1][j - 1] + (self.pattern[i - 1] != window[j - 1]) as usize;
+                dist[i][j] = diag.min(dist[i - 1][j] + 1).min(dist[i][j - 1] + 1);
+            }
+        }
+
+        let mut ops = Vec::with_capacity(m);
+        let (mut i, mut j) = (m, n);
+        while i > 0 {
+            let d = dist[i][j];
+            if j > 0 && self.pattern[i - 1] == window[j - 1] && d == dist[i - 1][j - 1] {
+                ops.push(Edit::Match);
+                i -= 1;
+                j -= 1;
+            } else if j > 0 && d == dist[i - 1][j - 1] + 1 {
+                ops.push(Edit::Subst);
+                i -= 1;
+                j -= 1;
+            } else if d == dist[i - 1][j] + 1 {
+                ops.push(Edit::Del);
+                i -= 1;
+            } else {
+                ops.push(Edit::Ins);
+                j -= 1;
+            }
+        }
+        ops.reverse();
+        Alignment { start: offset + j, end, dist: dist[m][n], ops }
+    }
+
+    /// Alignments of all occurrences with at most `k` edits, see
+    /// `find_iter` and `alignment`.
+    pub fn alignments(&self, text: &[u8], k: usize) -> Vec<Alignment> {
+        self.find_iter(text, k)
+            .map(|(end, _)| self.alignment(text, end))
+            .collect()
+    }
+
+}
+
+/// Edit operation turning the pattern into the text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Edit {
+    Match,
+    /// Pattern base replaced by another text base.
+    Subst,
+    /// Text base missing from the pattern.
+    Ins,
+    /// Pattern base missing from the text.
+    Del,
+}
+
+/// Alignment of the pattern to the text slice `start..end`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Alignment {
+    pub start: usize,
+    pub end: usize,
+    /// Edit distance, the number of edits other than `Edit::Match`.
+    pub dist: usize,
+    /// Edits, in pattern and text order.
+    pub ops: Vec<Edit>,
+}
+
+/// Iterator over approximate occurrences, see `Myers::find_iter`.
+pub struct MyersMatches<'a> {
+    myers: &'a Myers,
+    text: &'a [u8],
+    k: usize,
+    /// Number of bytes of the text read.
+    pos: usize,
+    /// Positive and negative vertical deltas of the last column of the
+    /// dynamic programming matrix.
+    pv: u64,
+    mv: u64,
+    /// Distance of the pattern to the best slice ending at `pos`.
+    dist: usize,
+}
+
+impl<'a> Iterator for MyersMatches<'a> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<(usize, usize)> {
+        let last = 1 << (self.myers.pattern.len() - 1);
+        while let Some(&b) = self.text.get(self.pos) {
+            let eq = self.myers.peq[b as usize];
+            let xv = eq | self.mv;
+            let xh = ((eq & self.pv).wrapping_add(self.pv) ^ self.pv) | eq;
+            let mut ph = self.mv | !(xh | self.pv);
+            let mut mh = self.pv & xh;
+            if ph & last != 0 {
+                self.dist += 1;
+            } else if mh & last != 0 {
+                self.dist -= 1;
+            }
+            // the first row is all zeros, occurrences may start anywhere
+            ph <<= 1;
+            mh <<= 1;
+            self.pv = mh | !(xv | ph);
+            self.mv = ph & xv;
+            self.pos += 1;
+            if self.dist <= self.k {
+                return Some((self.pos, self.dist));
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{Edit, Myers};
+    use data::Dataset;
+    use seq::{find_by, hamming_distance};
+
+    /// Distances of the pattern to the best slices ending at every
+    /// position, by dynamic programming over columns.
+    fn naive(text: &[u8], pattern: &[u8], k: usize) -> Vec<(usize, usize)> {
+        let mut column: Vec<usize> = (0..pattern.len() + 1).collect();
+        let mut hits = Vec::new();
+        for (j, &b) in text.iter().enumerate() {
+            let mut diag = column[0];
+            for i in 1..pattern.len() + 1 {
+                let d = (diag + (pattern[i - 1] != b) as usize)
+                    .min(column[i] + 1)
+                    .min(column[i - 1] + 1);
+                diag = column[i];
+                column[i] = d;
+            }
+            if column[pattern.len()] <= k {
+                hits.push((j + 1, column[pattern.len()]));
+            }
+        }
+        hits
+    }
+
+    #[test]
+    fn test_find() {
+        let text = b"ACGTTGCATGTCGCATGATGCATGAGAGCTACGATCGATCGGCATTACGGACTAC";
+        for &pattern in [&b"GCATG"[..], b"A", b"TTGCATCG", b"GATCGATCGAT", b"CCCCCC"].iter() {
+            for k in 0..4 {
+                assert_eq!(Myers::new(pattern).find(text, k), naive(text, pattern, k));
+            }
+        }
+        let long = [b'A'; 64];
+        assert_eq!(Myers::new(&long).find(&[b'A'; 70], 0).len(), 7);
+        assert_eq!(Myers::new(&long).find(&long[..60], 4), [(60, 4)]);
+    }
+
+    #[test]
+    fn test_approximate_pattern_matching() {
+        let dataset = Dataset::open_text("data/bioinformatics1/approximate_pattern_matching/dataset_9_4.txt");
+        let lines = dataset.lines();
+        let (pattern, text) = (lines[0].as_bytes(), lines[1].as_bytes());
+        let d = lines[2].parse::<usize>().unwrap();
+        let (starts, _) = find_by(text, pattern, |a, b| hamming_distance(a, b) <= d);
+        let ends = Myers::new(pattern).find(text, d);
+        // every occurrence with mismatches only is one with edits too
+        for start in starts {
+            let end = start + pattern.len();
+            assert!(ends.iter().any(|&(e, dist)| e == end && dist <= d));
+        }
+        assert!(!ends.is_empty());
+    }
+
+    #[test]
+    fn test_alignment() {
+        let myers = Myers::new(b"GATTACA");
+        let text = b"CCGATACATT";
+        assert_eq!(myers.find(text, 1), [(8, 1)]);
+        let alignment = myers.alignment(text, 8);
+        assert_eq!((alignment.start, alignment.end, alignment.dist), (2, 8, 1));
+        assert_eq!(alignment.ops.iter().filter(|&&op| op == Edit::Del).count(), 1);
+        assert_eq!(alignment.ops.len(), 7);
+
+        let text = b"GATTTACA";
+        let alignments = myers.alignments(text, 1);
+        assert_eq!(alignments.len(), 1);
+        assert_eq!(alignments[0].start, 0);
+        assert!(alignments[0].ops.contains(&Edit::Ins));
+    }
+
+}
@@ -1,30 +1,20 @@
 //! Algorithms on strings.
 
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::iter::Scan;
+use std::ops::Range;
 use std::str::Chars;
 
-use ::dna::Dna;
-
-static A: &'static str = "A";
-static T: &'static str = "T";
-static G: &'static str = "G";
-static C: &'static str = "C";
+use ::alphabet::{Alphabet, DnaAlphabet};
+use ::data;
+use ::data::stream::Chunk;
+use ::dna::{dust, Dna, Kmer, PackedDna};
+use ::seq::{self, Circular};
 
 /// Returns byte indexes of the first character of this string slice that
-/// matches the pattern.
-///
-/// # Panics
-///
-/// All characters should be `u8` ASCII
+/// matches the pattern, none if the pattern is longer than the text.
 fn indexes(text: &str, pat: &str) -> Vec<usize> {
-    let mut res = Vec::new();
-    for i in 0..text.len()-pat.len()+1 {
-        if &text[i..i+pat.len()] == pat {
-            res.push(i);
-        }
-    }
-    res
+    seq::find(text.as_bytes(), pat.as_bytes())
 }
 
 fn pattern_count(text: &str, pat: &str) -> usize {
@@ -42,13 +32,16 @@ fn frequent_words_count(text: &str, k: usize) -> Vec<usize> {
     count
 }
 
+/// Most frequent k-mers of `text`. Masked k-mers, see `dna::dust`, are
+/// skipped.
 pub fn frequent_words(text: &str, k: usize) -> Vec<&str> {
     let mut res = Vec::new();
     let count = frequent_words_count(text, k);
+    let unmasked = |i: &usize| !dust::has_masked(&text.as_bytes()[*i..*i+k]);
     // find maximum count
-    let max_count = *count.iter().max().unwrap();
+    let max_count = (0..count.len()).filter(&unmasked).map(|i| count[i]).max().unwrap_or(0);
     // read patterns with maximum count
-    for i in 0..count.len() {
+    for i in (0..count.len()).filter(&unmasked) {
         if count[i] == max_count {
             res.push(&text[i..i+k]);
         }
@@ -59,24 +52,32 @@ pub fn frequent_words(text: &str, k: usize) -> Vec<&str> {
     res
 }
 
-/// find most frequent k-mers with mismatches in a string.
+/// find most frequent k-mers with mismatches in a string. Masked k-mers,
+/// see `dna::dust`, are skipped, so fully masked text has none.
 pub fn frequent_words_with_mismatches(text: &str, k: usize, d: usize) -> Vec<String> {
     let mut res = HashSet::new();
     let len = 4usize.pow(k as u32);
     let mut close = vec![0; len];
 
     for i in 0..text.len()-k+1 {
-        let neighborhood = self::neighbors(&text[i..i+k], d);
+        if dust::has_masked(&text.as_bytes()[i..i+k]) {
+            continue;
+        }
+        let neighborhood = self::neighbors::<DnaAlphabet>(&text[i..i+k], d);
         for pattern in neighborhood {
-            let index = pattern_to_number(pattern.as_bytes());
+            let index = pattern_to_number::<DnaAlphabet>(pattern.as_bytes());
             close[index] += 1;
         }
     }
 
-    let max_count = *close.iter().max().unwrap();
+    // no unmasked k-mer, nothing is frequent
+    let max_count = close.iter().cloned().max().unwrap_or(0);
+    if max_count == 0 {
+        return Vec::new();
+    }
     for i in 0..len-1 {
         if close[i] == max_count {
-            let pattern = number_to_pattern(i, k);
+            let pattern = number_to_pattern::<DnaAlphabet>(i, k);
             res.insert(pattern);
         }
     }
@@ -84,6 +85,8 @@ pub fn frequent_words_with_mismatches(text: &str, k: usize, d: usize) -> Vec<Str
     res.into_iter().collect()
 }
 
+/// Most frequent k-mers with mismatches counting reverse complements too,
+/// masked k-mers are skipped as in `frequent_words_with_mismatches`.
 pub fn frequent_words_with_mismatches_and_reverse_complements(text: &str, k: usize, d: usize) -> Vec<String> {
     let mut res = HashSet::new();
     let len = 4usize.pow(k as u32);
@@ -91,19 +94,26 @@ pub fn frequent_words_with_mismatches_and_reverse_complements(text: &str, k: usi
 
     for i in 0..text.len()-k+1 {
         let kmer = &text[i..i+k];
+        if dust::has_masked(kmer.as_bytes()) {
+            continue;
+        }
         let rkmer = reverse_complement(kmer);
-        let n1 = self::neighbors(kmer, d);
-        let n2 = self::neighbors(&rkmer, d);
+        let n1 = self::neighbors::<DnaAlphabet>(kmer, d);
+        let n2 = self::neighbors::<DnaAlphabet>(&rkmer, d);
         for pattern in n1.iter().chain(n2.iter()) {
-            let index = pattern_to_number(pattern.as_bytes());
+            let index = pattern_to_number::<DnaAlphabet>(pattern.as_bytes());
             close[index] += 1;
         }
     }
 
-    let max_count = *close.iter().max().unwrap();
+    // no unmasked k-mer, nothing is frequent
+    let max_count = close.iter().cloned().max().unwrap_or(0);
+    if max_count == 0 {
+        return Vec::new();
+    }
     for i in 0..len-1 {
         if close[i] == max_count {
-            let pattern = number_to_pattern(i, k);
+            let pattern = number_to_pattern::<DnaAlphabet>(i, k);
             res.insert(pattern);
         }
     }
@@ -122,55 +132,136 @@ pub fn kmers(text: &str, k: usize) -> Vec<&str> {
     res
 }
 
-fn symbol_to_number(sym: u8) -> usize {
-    match sym as char {
-        'A' => 0,
-        'C' => 1,
-        'G' => 2,
-        'T' => 3,
-        c => panic!("symbol_to_number: invalid char {}", c)
+/// Number of occurrences of every k-mer in `text`. Unlike frequency
+/// arrays, only k-mers present in `text` take memory, so `k` can be up to
+/// `Kmer::MAX_LEN`. K-mers with bytes other than `A`, `C`, `G` and `T`
+/// are skipped.
+///
+/// If `canonical` is `true`, a k-mer and its reverse complement are
+/// counted together under `Kmer::canonical`.
+///
+/// # Examples
+///
+/// ```
+/// use bio::dna::Kmer;
+/// use bio::strings::kmer_counts;
+///
+/// let counts = kmer_counts(b"AACGTT", 2, true);
+/// assert_eq!(counts[&Kmer::from_slice(b"AA").unwrap()], 2);
+/// assert_eq!(counts.len(), 3);
+/// ```
+pub fn kmer_counts(text: &[u8], k: usize, canonical: bool) -> HashMap<Kmer, usize> {
+    let mut counts = HashMap::new();
+    for (_, kmer) in Kmer::iter(text, k) {
+        let kmer = if canonical { kmer.canonical() } else { kmer };
+        *counts.entry(kmer).or_insert(0) += 1;
     }
+    counts
 }
 
-fn number_to_symbol(n: usize) -> char {
-    match n {
-        0 => 'A',
-        1 => 'C',
-        2 => 'G',
-        3 => 'T',
-        x => panic!("number_to_symbol: invalid number {}", x)
-    }
+/// Rank of `pat` among all k-mers over alphabet `A` in lexicographic
+/// order of symbol ranks. For DNA it is the index of its `Kmer`.
+///
+/// # Panics
+///
+/// Panics if `pat` contains a symbol which is not in the alphabet, or if
+/// the rank overflows `usize`.
+///
+/// # Examples
+///
+/// ```
+/// use bio::alphabet::{DnaAlphabet, ProteinAlphabet};
+/// use bio::strings::{number_to_pattern, pattern_to_number};
+///
+/// assert_eq!(pattern_to_number::<DnaAlphabet>(b"GT"), 11);
+/// let index = pattern_to_number::<ProteinAlphabet>(b"MKV");
+/// assert_eq!(index, 10 * 400 + 8 * 20 + 17);
+/// assert_eq!(number_to_pattern::<ProteinAlphabet>(index, 3), "MKV");
+/// ```
+pub fn pattern_to_number<A: Alphabet>(pat: &[u8]) -> usize {
+    pat.iter().fold(0, |acc: usize, &sym| match A::rank(sym) {
+        Some(rank) => acc.checked_mul(A::size())
+            .and_then(|n| n.checked_add(rank))
+            .unwrap_or_else(|| panic!("pattern_to_number: {}-mer is too long", pat.len())),
+        None => panic!("pattern_to_number: invalid symbol {}", sym as char),
+    })
 }
 
-pub fn pattern_to_number(pat: &[u8]) -> usize {
-    if pat.len() == 0 {
-        0
-    } else {
-        let (init, last) = pat.split_at(pat.len()-1);
-        4 * pattern_to_number(init) + symbol_to_number(last[0])
+/// K-mer of length `k` over alphabet `A` with the given rank, inverse of
+/// `pattern_to_number`.
+pub fn number_to_pattern<A: Alphabet>(index: usize, k: usize) -> String {
+    let mut pat = vec![0; k];
+    let mut index = index;
+    for sym in pat.iter_mut().rev() {
+        *sym = A::symbol(index % A::size());
+        index /= A::size();
     }
+    assert!(index == 0, "number_to_pattern: index is too large for {}-mers", k);
+    pat.into_iter().map(|sym| sym as char).collect()
 }
 
-pub fn number_to_pattern(index: usize, k: usize) -> String {
-    if k == 1 {
-        let mut s = String::new();
-        s.push(number_to_symbol(index));
-        s
-    } else {
-        let (quot, rem) = (index / 4, index % 4);
-        let symbol = number_to_symbol(rem);
-        let mut pat = number_to_pattern(quot, k-1);
-        pat.push(symbol);
-        pat
+/// Frequency array of `A::size()^k` elements over alphabet `A` indexed by
+/// `pattern_to_number`. K-mers with symbols outside the alphabet, such as
+/// masked bases of DNA, see `dna::dust`, are skipped. See `kmer_counts`
+/// for DNA with large `k`.
+///
+/// # Examples
+///
+/// ```
+/// use bio::alphabet::RnaAlphabet;
+/// use bio::strings::{frequency_array, pattern_to_number};
+///
+/// let freqs = frequency_array::<RnaAlphabet>("AUGAUG", 2);
+/// assert_eq!(freqs.len(), 16);
+/// assert_eq!(freqs[pattern_to_number::<RnaAlphabet>(b"AU")], 2);
+/// ```
+pub fn frequency_array<A: Alphabet>(text: &str, k: usize) -> Vec<usize> {
+    let mut freqs = vec![0; A::size().pow(k as u32)];
+    let kmers = text.as_bytes().windows(k)
+        .filter(|kmer| kmer.iter().all(|&sym| A::rank(sym).is_some()));
+    for kmer in kmers {
+        freqs[pattern_to_number::<A>(kmer)] += 1;
     }
+    freqs
 }
 
-pub fn frequency_array(text: &str, k: usize) -> Vec<usize> {
+/// Frequency array of k-mers over a stream of sequence chunks, equal to
+/// `frequency_array` of the whole sequence. Chunks should overlap by at
+/// least `k - 1` bases.
+///
+/// # Examples
+///
+/// ```
+/// use bio::data::stream::Chunks;
+/// use bio::alphabet::DnaAlphabet;
+/// use bio::strings::{frequency_array, frequency_array_chunks};
+///
+/// let text = "ACGCGGCTCTGAAA";
+/// let chunks = Chunks::new(text.as_bytes(), 5, 1);
+/// assert_eq!(frequency_array_chunks(chunks, 2).unwrap(), frequency_array::<DnaAlphabet>(text, 2));
+/// ```
+pub fn frequency_array_chunks<I>(chunks: I, k: usize) -> data::Result<Vec<usize>>
+    where I: IntoIterator<Item = data::Result<Chunk>>,
+{
     let len = 4usize.pow(k as u32);
     let mut freqs = vec![0; len];
-    for i in 0..text.len()-k+1 {
-        let j = pattern_to_number(&text[i..i+k].as_bytes());
-        freqs[j] += 1;
+    for chunk in chunks {
+        let chunk = chunk?;
+        // windows starting in the overlap were counted with the previous chunk
+        let skip = (chunk.overlap + 1).saturating_sub(k);
+        for (_, kmer) in Kmer::iter(&chunk.seq[skip..], k) {
+            freqs[kmer.index()] += 1;
+        }
+    }
+    Ok(freqs)
+}
+
+/// Frequency array of k-mers of a packed sequence, equal to
+/// `frequency_array` of its text.
+pub fn frequency_array_packed(dna: &PackedDna, k: usize) -> Vec<usize> {
+    let mut freqs = vec![0; 4usize.pow(k as u32)];
+    for kmer in dna.kmers(k) {
+        freqs[kmer.index()] += 1;
     }
     freqs
 }
@@ -193,52 +284,151 @@ pub fn clump_finding_naive(text: &str, k: usize, l: usize, t: usize) -> Vec<&str
     res
 }
 
+/// K-mers forming `(l, t)`-clumps in `genome`, sorted: k-mers occurring
+/// at least `t` times within a window of length `l`.
 pub fn clump_finding(genome: &str, k: usize, l: usize, t: usize) -> Vec<String> {
-    let mut res = Vec::new();
-    let len = 4usize.pow(k as u32);
-    let mut clump = vec![0; len];
-    let text = &genome[0..l];
-    let mut freqs = frequency_array(text, k);
+    clump_kmers(clumps(Kmer::iter(genome.as_bytes(), k), k, l, t))
+}
 
-    for i in 0..len-1 {
-        if freqs[i] >= t {
-            clump[i] = 1;
-        }
-    }
+/// Regions of `(l, t)`-clumps in `genome` with their k-mers, sorted by
+/// position. A region spans `t` or more occurrences of its k-mer within a
+/// window of length `l`, overlapping regions of the same k-mer are
+/// merged. See `Interval::from_clumps` to write them as a track.
+///
+/// # Examples
+///
+/// ```
+/// use bio::strings::clump_regions;
+///
+/// let genome = "CTACGAACGTCAGTCCATGGAACGCACG";
+/// let regions = clump_regions(genome, 3, 8, 2);
+/// let regions: Vec<_> = regions.iter().map(|(kmer, r)| (kmer.as_str(), r.clone())).collect();
+/// assert_eq!(regions, [("ACG", 2..9), ("GTC", 8..15), ("ACG", 21..28)]);
+/// ```
+pub fn clump_regions(genome: &str, k: usize, l: usize, t: usize) -> Vec<(String, Range<usize>)> {
+    let mut regions: Vec<_> = clumps(Kmer::iter(genome.as_bytes(), k), k, l, t)
+        .into_iter()
+        .flat_map(|(kmer, regions)| {
+            let kmer = kmer.to_string();
+            regions.into_iter().map(move |r| (kmer.clone(), r))
+        })
+        .collect();
+    regions.sort_by(|a, b| (a.1.start, &a.0).cmp(&(b.1.start, &b.0)));
+    regions
+}
 
-    for i in 1..genome.len()-l+1 {
-        let first_pat = &genome[i-1..i-1+k];
-        let index = pattern_to_number(first_pat.as_bytes());
-        freqs[index] -= 1;
+/// K-mers forming `(l, t)`-clumps in a circular `genome`, including
+/// clumps in windows spanning the end of the genome. `l` should not
+/// exceed the genome length.
+///
+/// # Examples
+///
+/// ```
+/// use bio::strings::{clump_finding, clump_finding_circular};
+///
+/// let genome = "ACGTTCAGGATACG";
+/// assert!(clump_finding(genome, 3, 8, 2).is_empty());
+/// assert_eq!(clump_finding_circular(genome, 3, 8, 2), ["ACG"]);
+/// ```
+pub fn clump_finding_circular(genome: &str, k: usize, l: usize, t: usize) -> Vec<String> {
+    let text = Circular::new(genome.as_bytes()).extended(l - 1);
+    clump_kmers(clumps(Kmer::iter(&text, k), k, l, t))
+}
 
-        let last_pat = &genome[i+l-k..i+l];
-        let index = pattern_to_number(last_pat.as_bytes());
-        freqs[index] += 1;
+/// K-mers forming `(l, t)`-clumps in a packed genome, sorted. Unlike
+/// `clump_finding`, the window slides over packed k-mers without copying
+/// the genome text.
+///
+/// # Examples
+///
+/// ```
+/// use bio::dna::PackedDna;
+/// use bio::strings::clump_finding_packed;
+///
+/// let genome = PackedDna::from_slice(b"CGGACTCGACAGATGTGAAGAACGACAATGTGAAGACTCGACACGACAGAGTGAAGAGAAGAGGAAACATTGTAA").unwrap();
+/// assert_eq!(clump_finding_packed(&genome, 5, 50, 4), ["CGACA", "GAAGA"]);
+/// ```
+pub fn clump_finding_packed(genome: &PackedDna, k: usize, l: usize, t: usize) -> Vec<String> {
+    clump_kmers(clumps(genome.kmers(k).enumerate(), k, l, t))
+}
 
-        if freqs[index] >= t {
-            clump[index] = 1;
+/// K-mers occurring at least `t` times within a window of length `l`,
+/// with their merged regions, given positioned `kmers` in increasing
+/// order of positions.
+fn clumps<I>(kmers: I, k: usize, l: usize, t: usize) -> BTreeMap<Kmer, Vec<Range<usize>>>
+    where I: IntoIterator<Item = (usize, Kmer)>,
+{
+    let mut window = VecDeque::new();
+    let mut positions: HashMap<Kmer, VecDeque<usize>> = HashMap::new();
+    let mut found: BTreeMap<Kmer, Vec<Range<usize>>> = BTreeMap::new();
+
+    for (pos, kmer) in kmers {
+        // drop k-mers which do not fit into a window with the new one
+        while let Some(&(first, old)) = window.front() {
+            if first + l >= pos + k {
+                break;
+            }
+            window.pop_front();
+            positions.get_mut(&old).unwrap().pop_front();
         }
-    }
-
-    for i in 0..len-1 {
-        if clump[i] == 1 {
-            let pat = number_to_pattern(i, k);
-            res.push(pat);
+        window.push_back((pos, kmer));
+        let occurrences = positions.entry(kmer).or_default();
+        occurrences.push_back(pos);
+        if occurrences.len() >= t {
+            let region = occurrences[0]..pos + k;
+            let regions = found.entry(kmer).or_default();
+            match regions.last_mut() {
+                Some(last) if last.end >= region.start => last.end = region.end,
+                _ => regions.push(region),
+            }
         }
     }
 
-    res.sort();
-    res.dedup();
-    res
+    found
+}
+
+/// Sorted k-mers of `clumps`.
+fn clump_kmers(clumps: BTreeMap<Kmer, Vec<Range<usize>>>) -> Vec<String> {
+    clumps.keys().map(|kmer| kmer.to_string()).collect()
+}
+
+/// Running difference between the number of `G` and `C` in `genome`.
+/// Accepts anything viewable as bytes, such as `&str`, `Dna` or
+/// `MappedDna`.
+pub fn gc_skew<'a, S>(genome: &'a S) -> Box<Iterator<Item=isize> + 'a>
+    where S: AsRef<[u8]> + ?Sized,
+{
+    skew(genome.as_ref().iter())
 }
 
-pub fn gc_skew<'a>(genome: &'a str) -> Box<Iterator<Item=isize> + 'a> {
+/// Running difference between the number of `G` and `C` along a circular
+/// `genome`, starting at position `start` and wrapping around the end, so
+/// that the skew diagram can be drawn from any origin.
+///
+/// # Examples
+///
+/// ```
+/// use bio::seq::min_indices;
+/// use bio::strings::gc_skew_circular;
+///
+/// let skew: Vec<_> = gc_skew_circular("GGCCAC", 4).collect();
+/// assert_eq!(skew, [0, -1, 0, 1, 0, -1]);
+/// assert_eq!(min_indices(skew), [1, 5]);
+/// ```
+pub fn gc_skew_circular<'a, S>(genome: &'a S, start: usize) -> Box<dyn Iterator<Item = isize> + 'a>
+    where S: AsRef<[u8]> + ?Sized,
+{
+    skew(Circular::new(genome.as_ref()).iter_from(start))
+}
+
+fn skew<'a, I>(genome: I) -> Box<dyn Iterator<Item = isize> + 'a>
+    where I: Iterator<Item = &'a u8> + 'a,
+{
     let iter = genome
-        .chars()
-        .scan(0, |acc, c| {
+        .scan(0, |acc, &c| {
             *acc = match c {
-                'G' => *acc + 1,
-                'C' => *acc - 1,
+                b'G' => *acc + 1,
+                b'C' => *acc - 1,
                 _ => *acc
             };
             Some(*acc)
@@ -270,33 +460,28 @@ pub fn hamming_distance(xs: &str, ys: &str) -> usize {
     })
 }
 
-pub fn neighbors(pattern: &str, d: usize) -> Vec<String> {
+/// All k-mers over alphabet `A` within Hamming distance `d` of `pattern`.
+pub fn neighbors<A: Alphabet>(pattern: &str, d: usize) -> Vec<String> {
     let mut res = HashSet::new();
     if d == 0 {
-        res.insert(pattern.to_string());
-        res.into_iter().collect()
+        res.insert(pattern.to_owned());
     } else if pattern.len() == 1 {
-        res.insert(A.to_owned());
-        res.insert(T.to_owned());
-        res.insert(G.to_owned());
-        res.insert(C.to_owned());
-        res.into_iter().collect()
+        for &sym in A::SYMBOLS.iter() {
+            res.insert((sym as char).to_string());
+        }
     } else {
         let tail = &pattern[1..];
-        let suffix = neighbors(tail, d);
-        for text in suffix.iter() {
-            if hamming_distance(tail, text) < d {
-                res.insert(A.to_owned() + text);
-                res.insert(T.to_owned() + text);
-                res.insert(G.to_owned() + text);
-                res.insert(C.to_owned() + text);
+        for suffix in neighbors::<A>(tail, d).iter() {
+            if hamming_distance(tail, suffix) < d {
+                for &sym in A::SYMBOLS.iter() {
+                    res.insert(format!("{}{}", sym as char, suffix));
+                }
             } else {
-                let h = pattern[0..1].to_owned();
-                res.insert(h + text);
+                res.insert(pattern[..1].to_owned() + suffix);
             }
         }
-        res.into_iter().collect()
     }
+    res.into_iter().collect()
 }
 
 fn reverse_complement(text: &str) -> String {
@@ -308,7 +493,13 @@ mod tests {
 
     use test::Bencher;
 
+    use std::str;
+
+    use alphabet::DnaAlphabet;
     use data::Dataset;
+    use data::stream::Chunks;
+    use dna::PackedDna;
+    use dna::dust::Dust;
 
     #[test]
     fn test_indexes() {
@@ -317,6 +508,67 @@ mod tests {
         let lines = dataset.lines();
         let (text, pattern) = (lines[0], lines[1]);
         assert_eq!(super::indexes(text, pattern).len(), answer.parse::<usize>().unwrap());
+        assert!(super::indexes("ACG", "ACGT").is_empty());
+    }
+
+    #[test]
+    fn test_packed() {
+        let text = "CGGACTCGACAGATGTGAAGAACGACAATGTGAAGACTCGACACGACAGAGTGAAGAGAAGAGGAAACATTGTAA";
+        let packed = PackedDna::from_slice(text.as_bytes()).unwrap();
+        for k in 1..6 {
+            assert_eq!(super::frequency_array_packed(&packed, k), super::frequency_array::<DnaAlphabet>(text, k));
+        }
+        assert_eq!(super::clump_finding_packed(&packed, 5, 50, 4), super::clump_finding(text, 5, 50, 4));
+        let mut kmers: Vec<_> = super::clump_regions(text, 5, 50, 4).into_iter().map(|(kmer, _)| kmer).collect();
+        kmers.sort();
+        kmers.dedup();
+        assert_eq!(kmers, super::clump_finding(text, 5, 50, 4));
+    }
+
+    #[test]
+    fn test_chunks() {
+        let text = "ACGTNNACGGTCAacgtTTGACNGTACG";
+        for k in 1..5 {
+            for &(size, overlap) in [(5, 4), (7, 3), (30, 0)].iter() {
+                if overlap + 1 < k {
+                    continue;
+                }
+                let chunks = Chunks::new(text.as_bytes(), size, overlap).preserve_case();
+                assert_eq!(super::frequency_array_chunks(chunks, k).unwrap(), super::frequency_array::<DnaAlphabet>(text, k));
+            }
+        }
+    }
+
+    #[test]
+    fn test_masked() {
+        let mut text = b"ACGTTACGAAAAAAAAAAAAAAAACGTTACG".to_vec();
+        assert_eq!(super::frequent_words(str::from_utf8(&text).unwrap(), 3), ["AAA"]);
+        Dust::new().soft_mask(&mut text);
+        let text = str::from_utf8(&text).unwrap();
+        assert_eq!(super::frequent_words(text, 3), ["ACG"]);
+        assert_eq!(super::frequent_words_with_mismatches(text, 3, 0), ["ACG"]);
+        assert_eq!(super::frequency_array::<DnaAlphabet>(text, 3)[super::pattern_to_number::<DnaAlphabet>(b"AAA")], 0);
+
+        assert!(super::frequent_words_with_mismatches("acgtacgt", 2, 0).is_empty());
+        assert!(super::frequent_words_with_mismatches_and_reverse_complements("acgtNNNN", 2, 1).is_empty());
+        assert!(super::frequency_array::<DnaAlphabet>("acgtNNNN", 2).iter().all(|&n| n == 0));
+    }
+
+    #[test]
+    fn test_circular() {
+        let text = "CGGACTCGACAGATGTGAAGAACGACAATGTGAAGACTCGACACGACAGAGTGAAGAGAAGAGGAAACATTGTAA";
+        // rotating a circular genome does not change its clumps
+        let (head, tail) = text.split_at(30);
+        let rotated = format!("{}{}", tail, head);
+        let clumps = super::clump_finding_circular(text, 5, 50, 4);
+        assert_eq!(super::clump_finding_circular(&rotated, 5, 50, 4), clumps);
+        for clump in super::clump_finding(text, 5, 50, 4) {
+            assert!(clumps.contains(&clump));
+        }
+
+        let skew: Vec<_> = super::gc_skew_circular(text, 30).collect();
+        assert_eq!(skew.len(), text.len());
+        assert_eq!(skew[skew.len() - 1], super::gc_skew(text).last().unwrap());
     }
 
     #[bench]